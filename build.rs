@@ -22,7 +22,7 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 
 // When updating the ImageMagick version, also rebuild the docsrs_bindings.rs file.
-const MIN_VERSION: &str = "7.1.1";
+const MIN_VERSION: &str = "7.0";
 const MAX_VERSION: &str = "7.2";
 
 #[cfg(windows)]
@@ -40,6 +40,33 @@ typedef long ssize_t;
 #[cfg(not(windows))]
 static HEADER: &str = "#include <MagickWand/MagickWand.h>\n";
 
+// MagickWand releases required by functions wrapped by this crate, as
+// (major, minor, micro, patch). For every release no newer than the headers
+// the bindings were generated from, a `magick_<major>_<minor>_<micro>_<patch>`
+// cfg is set, so wrappers of newer functions can be compiled only when the
+// installed library has them.
+//
+// Every function gated on a cfg is listed with it. Where the release that
+// introduced a function is not known exactly, its entry is a release known to
+// have it, so the gate may be stricter than necessary but never too lax.
+const VERSION_CFGS: &[(u32, u32, u32, u32)] = &[
+    // MagickAutoThresholdImage, MagickKuwaharaImage, MagickLocalContrastImage,
+    // MagickMeanShiftImage, MagickWaveletDenoiseImage
+    (7, 0, 8, 0),
+    // MagickCLAHEImage
+    (7, 0, 9, 0),
+    // MagickKmeansImage, MagickColorThresholdImage, MagickRangeThresholdImage
+    (7, 0, 10, 37),
+    // MagickWhiteBalanceImage, MagickConnectedComponentsImage (for the
+    // `census` and `merge` fields of CCObjectInfo)
+    (7, 1, 0, 0),
+    // DrawGetTypeMetrics
+    (7, 1, 1, 26),
+];
+
+// Quantum depths ImageMagick can be configured with.
+const QUANTUM_DEPTHS: &[&str] = &["8", "16", "32", "64"];
+
 //on windows path env always contain : like c:
 pub const PATH_SEPARATOR: &str = if cfg!(target_os = "windows") {
     ";"
@@ -114,6 +141,7 @@ fn main() {
         println!("cargo:rerun-if-changed={}", prebuilt.to_string_lossy());
         std::fs::copy(&prebuilt, out_dir.join("bindings.rs"))
             .expect("could not copy pre-generated docs.rs bindings into OUT_DIR");
+        emit_build_config_cfgs(&out_dir.join("bindings.rs"));
         return;
     }
 
@@ -318,6 +346,77 @@ fn main() {
 
         std::fs::remove_file(&gen_h_path).expect("could not remove header file");
     }
+
+    emit_build_config_cfgs(&bindings_path_str);
+}
+
+/// Emit the `magick_*` version cfgs, `magick_hdri`, and
+/// `magick_quantum_depth`, as found in the version and configuration
+/// constants of the generated bindings.
+fn emit_build_config_cfgs(bindings_path: &Path) {
+    for (major, minor, micro, patch) in VERSION_CFGS {
+        println!("cargo:rustc-check-cfg=cfg(magick_{major}_{minor}_{micro}_{patch})");
+    }
+    println!("cargo:rustc-check-cfg=cfg(magick_hdri)");
+    println!(
+        "cargo:rustc-check-cfg=cfg(magick_quantum_depth, values(\"{}\"))",
+        QUANTUM_DEPTHS.join("\", \"")
+    );
+
+    let bindings = std::fs::read_to_string(bindings_path).expect("could not read bindings file");
+    let version = binding_bytes_const(&bindings, "MagickLibVersionText")
+        .and_then(|text| {
+            let addendum = binding_bytes_const(&bindings, "MagickLibAddendum").unwrap_or_default();
+            parse_version(&text, &addendum)
+        })
+        .expect("could not determine the MagickWand version from the bindings");
+    for &(major, minor, micro, patch) in VERSION_CFGS {
+        if version >= (major, minor, micro, patch) {
+            println!("cargo:rustc-cfg=magick_{major}_{minor}_{micro}_{patch}");
+        }
+    }
+    if binding_int_const(&bindings, "MAGICKCORE_HDRI_ENABLE").is_some_and(|hdri| hdri != 0) {
+        println!("cargo:rustc-cfg=magick_hdri");
+    }
+    if let Some(depth) = binding_int_const(&bindings, "MAGICKCORE_QUANTUM_DEPTH") {
+        println!("cargo:rustc-cfg=magick_quantum_depth=\"{depth}\"");
+    }
+}
+
+/// The right hand side of `pub const <name>: <type> = <value>;` in the bindings.
+fn binding_const<'a>(bindings: &'a str, name: &str) -> Option<&'a str> {
+    let prefix = format!("pub const {name}:");
+    bindings.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix(&prefix)?;
+        let (_, value) = rest.split_once('=')?;
+        Some(value.trim().trim_end_matches(';').trim())
+    })
+}
+
+fn binding_int_const(bindings: &str, name: &str) -> Option<u32> {
+    binding_const(bindings, name)?.parse().ok()
+}
+
+/// A C string constant, which bindgen writes as `b"7.1.2\0"`.
+fn binding_bytes_const(bindings: &str, name: &str) -> Option<String> {
+    let value = binding_const(bindings, name)?;
+    let text = value.strip_prefix("b\"")?.strip_suffix('"')?;
+    Some(text.trim_end_matches("\\0").to_string())
+}
+
+/// Parse `7.1.2` and an addendum such as `-25` into a version tuple.
+fn parse_version(text: &str, addendum: &str) -> Option<(u32, u32, u32, u32)> {
+    let mut parts = text.split('.').map(|part| part.parse::<u32>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().flatten().unwrap_or(0);
+    let micro = parts.next().flatten().unwrap_or(0);
+    let patch = addendum
+        .trim_start_matches('-')
+        .split(|c: char| !c.is_ascii_digit())
+        .next()
+        .and_then(|patch| patch.parse().ok())
+        .unwrap_or(0);
+    Some((major, minor, micro, patch))
 }
 
 fn env_var_set_default(name: &str, value: &str) {
@@ -12,7 +12,7 @@ fn add_border(filepath: &str, border_color: &str) -> Result<Vec<u8>, MagickError
         magick_wand_genesis();
     });
 
-    let wand = MagickWand::new();
+    let mut wand = MagickWand::new();
     wand.read_image(filepath)?;
 
     let mut border = PixelWand::new();
@@ -18,7 +18,7 @@ fn pdf_to_png(filepath: &str, output_prefix: &str) -> Result<(), MagickError> {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
+    let mut wand = MagickWand::new();
 
     // `-density 300`: set the resolution BEFORE reading so the PDF is
     // rasterized at 300 DPI rather than the default (typically 72 DPI).
@@ -15,7 +15,7 @@ fn resize(filepath: &str) -> Result<Vec<u8>, MagickError> {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
+    let mut wand = MagickWand::new();
     wand.read_image(filepath)?;
     wand.fit(240, 240);
     wand.write_image_blob("jpeg")
@@ -30,7 +30,7 @@ fn trim_blur_crop(input: &str, output: &str) -> Result<(), MagickError> {
     });
 
     // The image we will ultimately crop and save.
-    let wand = MagickWand::new();
+    let mut wand = MagickWand::new();
     wand.read_image(input)?;
 
     // A throwaway blurred copy used only to measure where the border ends.
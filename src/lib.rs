@@ -33,14 +33,22 @@ use libc::size_t;
 use std::ffi::{CStr, CString};
 use std::slice::from_raw_parts;
 
+#[cfg(any(feature = "log", feature = "tracing"))]
+pub use crate::logging::{DEFAULT_LOG_EVENTS, install_log_handler};
+pub use crate::quantum::{HDRI_ENABLED, QUANTUM_DEPTH, QUANTUM_RANGE, Quantum};
 pub use crate::result::MagickError;
 use crate::result::Result;
 pub use crate::types::*;
+pub use crate::version::{MagickVersion, magick_version};
 pub use crate::wand::*;
 
 mod conversions;
+#[cfg(any(feature = "log", feature = "tracing"))]
+mod logging;
+mod quantum;
 mod result;
 mod types;
+mod version;
 mod wand;
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
@@ -94,3 +102,75 @@ pub fn magick_query_fonts(pattern: &str) -> Result<Vec<String>> {
         Ok(v)
     }
 }
+
+/// Return the threshold maps for ordered dithering that
+/// [`MagickWand::ordered_dither_image`] accepts, as defined by ImageMagick's
+/// `thresholds.xml` configuration files.
+///
+/// Only available on Unix: ImageMagick writes the list to a C `FILE`, which
+/// cannot safely be handed across the C runtimes of a Windows build.
+#[cfg(unix)]
+pub fn magick_threshold_maps() -> Result<Vec<ThresholdMap>> {
+    // ListThresholdMaps only writes to a stdio stream, so the listing goes
+    // through a temporary file.
+    let file = unsafe { libc::tmpfile() };
+    if file.is_null() {
+        return Err(MagickError(
+            "could not create a temporary file for the threshold maps".to_string(),
+        ));
+    }
+    let (listed, error) = unsafe {
+        let exception = bindings::AcquireExceptionInfo();
+        let listed = bindings::ListThresholdMaps(file as *mut bindings::FILE, exception);
+        let error = exception_message(&*exception);
+        bindings::DestroyExceptionInfo(exception);
+        (listed, error)
+    };
+    let mut listing = Vec::new();
+    let mut buffer = [0u8; 4096];
+    unsafe { libc::rewind(file) };
+    loop {
+        let read = unsafe {
+            libc::fread(
+                buffer.as_mut_ptr() as *mut libc::c_void,
+                1,
+                buffer.len(),
+                file,
+            )
+        };
+        if read == 0 {
+            break;
+        }
+        listing.extend_from_slice(&buffer[..read]);
+    }
+    unsafe { libc::fclose(file) };
+    match listed {
+        bindings::MagickBooleanType::MagickTrue => {
+            Ok(ThresholdMap::parse_list(&String::from_utf8_lossy(&listing)))
+        }
+        _ => Err(MagickError(error.unwrap_or_else(|| {
+            "could not list the threshold maps".to_string()
+        }))),
+    }
+}
+
+/// The reason and description of an exception, formatted as ImageMagick does,
+/// or `None` if no exception was thrown.
+#[cfg(unix)]
+fn exception_message(exception: &bindings::ExceptionInfo) -> Option<String> {
+    if exception.severity == bindings::ExceptionType::UndefinedException {
+        return None;
+    }
+    let text = |ptr: *const libc::c_char| {
+        (!ptr.is_null()).then(|| {
+            unsafe { CStr::from_ptr(ptr) }
+                .to_string_lossy()
+                .into_owned()
+        })
+    };
+    let reason = text(exception.reason).unwrap_or_default();
+    Some(match text(exception.description) {
+        Some(description) => format!("{reason} ({description})"),
+        None => reason,
+    })
+}
@@ -0,0 +1,150 @@
+/*
+ * Copyright 2026 Nathan Fiedler
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//!
+//! Forwarding of ImageMagick's own log events to the `log` or `tracing` crate.
+//!
+use std::ffi::{CStr, CString};
+
+use libc::{c_char, c_uint};
+
+use crate::bindings;
+use crate::result::{MagickError, Result};
+
+/// The event categories forwarded when no other list is given: the ones that
+/// explain why a decode or encode failed.
+pub const DEFAULT_LOG_EVENTS: &str = "Coder,Resource,Cache,Policy,Exception";
+
+/// Severity assigned to a forwarded event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Level {
+    Warn,
+    Debug,
+    Trace,
+}
+
+/// Map a raw `LogEventType` value to the name ImageMagick uses for it (lower
+/// cased, as used in the forwarded target) and a severity.
+fn classify(event: c_uint) -> (&'static str, Level) {
+    use bindings::LogEventType as E;
+    // Compare against the raw discriminants: ImageMagick may pass values that
+    // are not valid `LogEventType` variants, which must never be materialized
+    // as the Rust enum.
+    match event {
+        e if e == E::ExceptionEvent as c_uint => ("exception", Level::Warn),
+        e if e == E::DeprecateEvent as c_uint => ("deprecate", Level::Warn),
+        e if e == E::CoderEvent as c_uint => ("coder", Level::Debug),
+        e if e == E::BlobEvent as c_uint => ("blob", Level::Debug),
+        e if e == E::ConfigureEvent as c_uint => ("configure", Level::Debug),
+        e if e == E::ModuleEvent as c_uint => ("module", Level::Debug),
+        e if e == E::PolicyEvent as c_uint => ("policy", Level::Debug),
+        e if e == E::ResourceEvent as c_uint => ("resource", Level::Debug),
+        e if e == E::LocaleEvent as c_uint => ("locale", Level::Debug),
+        e if e == E::AnnotateEvent as c_uint => ("annotate", Level::Debug),
+        e if e == E::DrawEvent as c_uint => ("draw", Level::Debug),
+        e if e == E::ImageEvent as c_uint => ("image", Level::Debug),
+        e if e == E::TransformEvent as c_uint => ("transform", Level::Debug),
+        e if e == E::CommandEvent as c_uint => ("command", Level::Debug),
+        e if e == E::WandEvent as c_uint => ("wand", Level::Debug),
+        e if e == E::UserEvent as c_uint => ("user", Level::Debug),
+        e if e == E::X11Event as c_uint => ("x11", Level::Debug),
+        e if e == E::CacheEvent as c_uint => ("cache", Level::Trace),
+        e if e == E::PixelEvent as c_uint => ("pixel", Level::Trace),
+        e if e == E::AccelerateEvent as c_uint => ("accelerate", Level::Trace),
+        e if e == E::TraceEvent as c_uint => ("trace", Level::Trace),
+        _ => ("other", Level::Debug),
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn forward(event: &'static str, level: Level, message: &str) {
+    match level {
+        Level::Warn => tracing::warn!(target: "magick_rust", event, "{message}"),
+        Level::Debug => tracing::debug!(target: "magick_rust", event, "{message}"),
+        Level::Trace => tracing::trace!(target: "magick_rust", event, "{message}"),
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+fn forward(event: &'static str, level: Level, message: &str) {
+    let level = match level {
+        Level::Warn => log::Level::Warn,
+        Level::Debug => log::Level::Debug,
+        Level::Trace => log::Level::Trace,
+    };
+    log::log!(target: &format!("magick_rust::{event}"), level, "{message}");
+}
+
+/// The `MagickLogMethod` installed by [`install_log_handler`]. The event type
+/// is received as a plain integer; see `classify`.
+unsafe extern "C" fn log_method(event: c_uint, text: *const c_char) {
+    if text.is_null() {
+        return;
+    }
+    let message = unsafe { CStr::from_ptr(text) }.to_string_lossy();
+    let (event, level) = classify(event);
+    forward(event, level, message.trim_end());
+}
+
+/// Route ImageMagick's internal log events to the `log` crate (or, with the
+/// `tracing` feature, to `tracing`), so that coder, resource, cache, and policy
+/// activity leading up to a failure shows up in the application's logs.
+///
+/// `events` selects the event categories using the same syntax as the
+/// ImageMagick command line's `-debug` option: a comma separated list such as
+/// `"Coder,Policy"`, or `"All"`; see [`DEFAULT_LOG_EVENTS`] for a sensible
+/// choice. Passing `"None"` turns event logging off again.
+///
+/// With the `log` crate, each event is logged with the target
+/// `magick_rust::<event>` (e.g. `magick_rust::coder`); with `tracing`, the
+/// target is `magick_rust` and the category is recorded in the `event` field.
+/// Exceptions and deprecation notices are logged as warnings, cache, pixel,
+/// and trace events at trace level, and everything else at debug level.
+///
+/// Must be called after [`magick_wand_genesis`](crate::magick_wand_genesis).
+/// The handler is process-wide, like ImageMagick's logging itself.
+pub fn install_log_handler(events: &str) -> Result<()> {
+    if unsafe { bindings::IsMagickWandInstantiated() } == bindings::MagickBooleanType::MagickFalse {
+        return Err(MagickError(
+            "magick_wand_genesis() must be called before install_log_handler()".to_string(),
+        ));
+    }
+    let c_events = CString::new(events).map_err(|_| "events string contains null byte")?;
+    let c_format = CString::new("%m").unwrap();
+    // SAFETY: `log_method` has the same ABI as `MagickLogMethod`; the only
+    // difference is that the enum argument is taken as the integer ImageMagick
+    // actually passes (`LogEventType` is a 32-bit C enum).
+    let method: unsafe extern "C" fn(bindings::LogEventType, *const c_char) = unsafe {
+        std::mem::transmute::<
+            unsafe extern "C" fn(c_uint, *const c_char),
+            unsafe extern "C" fn(bindings::LogEventType, *const c_char),
+        >(log_method)
+    };
+    // SAFETY: likewise, `SetLogEventMask` returns the combined event mask,
+    // which is generally not a valid `LogEventType` variant, so it is called
+    // through a pointer that receives the result as an integer.
+    let set_log_event_mask: unsafe extern "C" fn(*const c_char) -> c_uint = unsafe {
+        std::mem::transmute::<
+            unsafe extern "C" fn(*const c_char) -> bindings::LogEventType,
+            unsafe extern "C" fn(*const c_char) -> c_uint,
+        >(bindings::SetLogEventMask)
+    };
+    unsafe {
+        bindings::SetLogFormat(c_format.as_ptr());
+        bindings::SetLogMethod(Some(method));
+        set_log_event_mask(c_events.as_ptr());
+    }
+    Ok(())
+}
@@ -0,0 +1,53 @@
+/*
+ * Copyright 2026 Nathan Fiedler
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//!
+//! The pixel component type of the ImageMagick build the bindings were
+//! generated from.
+//!
+use crate::bindings;
+
+/// The type of a single pixel component, as used by the `*_quantum` accessors
+/// of `PixelWand`.
+///
+/// This depends on how ImageMagick was configured: with HDRI (the default for
+/// ImageMagick 7) it is a floating point type (`f32` for Q8 and Q16, `f64` for
+/// Q32 and Q64), whose values may be negative or exceed [`QUANTUM_RANGE`];
+/// without HDRI it is an unsigned integer of the quantum depth (`u8`, `u16`,
+/// `u32`, or `u64`), and values are clamped to `0..=QUANTUM_RANGE`.
+pub type Quantum = bindings::Quantum;
+
+/// Whether ImageMagick was built with high dynamic range imaging
+/// (`MAGICKCORE_HDRI_ENABLE`). The same information is available to
+/// conditional compilation as the `magick_hdri` cfg.
+pub const HDRI_ENABLED: bool = bindings::MAGICKCORE_HDRI_ENABLE != 0;
+
+/// Bits per pixel component (`MAGICKCORE_QUANTUM_DEPTH`), also available to
+/// conditional compilation as the `magick_quantum_depth` cfg.
+pub const QUANTUM_DEPTH: usize = bindings::MAGICKCORE_QUANTUM_DEPTH as usize;
+
+/// The value of a fully saturated pixel component, `2^QUANTUM_DEPTH - 1`
+/// (ImageMagick's `QuantumRange`, which bindgen cannot translate).
+///
+/// Raw quantum values returned by the crate, such as those of
+/// [`MagickWand::get_image_range`](crate::MagickWand::get_image_range), are
+/// relative to this value; divide by it to normalize them. Under HDRI they
+/// may fall outside `0.0..=QUANTUM_RANGE`.
+pub const QUANTUM_RANGE: f64 = match QUANTUM_DEPTH {
+    8 => 255.0,
+    16 => 65535.0,
+    32 => 4294967295.0,
+    _ => 18446744073709551615.0,
+};
@@ -0,0 +1,133 @@
+/*
+ * Copyright 2026 Nathan Fiedler
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use crate::bindings;
+
+/// A 2D affine transformation, as used by
+/// [`DrawingWand::affine`](crate::DrawingWand::affine).
+///
+/// A point `(x, y)` is transformed to
+/// `(sx * x + ry * y + tx, rx * x + sy * y + ty)`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AffineMatrix {
+    /// Horizontal scale.
+    pub sx: f64,
+    /// Vertical shear (rotation) factor.
+    pub rx: f64,
+    /// Horizontal shear (rotation) factor.
+    pub ry: f64,
+    /// Vertical scale.
+    pub sy: f64,
+    /// Horizontal translation.
+    pub tx: f64,
+    /// Vertical translation.
+    pub ty: f64,
+}
+
+impl AffineMatrix {
+    /// The transformation that leaves every point in place.
+    pub const IDENTITY: AffineMatrix = AffineMatrix {
+        sx: 1.0,
+        rx: 0.0,
+        ry: 0.0,
+        sy: 1.0,
+        tx: 0.0,
+        ty: 0.0,
+    };
+
+    /// Move by `(tx, ty)`.
+    pub fn translation(tx: f64, ty: f64) -> Self {
+        AffineMatrix {
+            tx,
+            ty,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Scale by `sx` horizontally and `sy` vertically.
+    pub fn scaling(sx: f64, sy: f64) -> Self {
+        AffineMatrix {
+            sx,
+            sy,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Rotate clockwise (in image coordinates, where y points down) by
+    /// `degrees` around the origin.
+    pub fn rotation(degrees: f64) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        AffineMatrix {
+            sx: cos,
+            rx: sin,
+            ry: -sin,
+            sy: cos,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Slant vertical lines by `degrees`.
+    pub fn skew_x(degrees: f64) -> Self {
+        AffineMatrix {
+            ry: degrees.to_radians().tan(),
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Slant horizontal lines by `degrees`.
+    pub fn skew_y(degrees: f64) -> Self {
+        AffineMatrix {
+            rx: degrees.to_radians().tan(),
+            ..Self::IDENTITY
+        }
+    }
+
+    /// The transformation that applies `self` first and then `next`.
+    pub fn then(&self, next: &AffineMatrix) -> Self {
+        AffineMatrix {
+            sx: next.sx * self.sx + next.ry * self.rx,
+            rx: next.rx * self.sx + next.sy * self.rx,
+            ry: next.sx * self.ry + next.ry * self.sy,
+            sy: next.rx * self.ry + next.sy * self.sy,
+            tx: next.sx * self.tx + next.ry * self.ty + next.tx,
+            ty: next.rx * self.tx + next.sy * self.ty + next.ty,
+        }
+    }
+
+    /// Transform the point `(x, y)`.
+    pub fn transform_point(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (
+            self.sx * x + self.ry * y + self.tx,
+            self.rx * x + self.sy * y + self.ty,
+        )
+    }
+
+    pub(crate) fn to_raw(self) -> bindings::AffineMatrix {
+        bindings::AffineMatrix {
+            sx: self.sx,
+            rx: self.rx,
+            ry: self.ry,
+            sy: self.sy,
+            tx: self.tx,
+            ty: self.ty,
+        }
+    }
+}
+
+impl Default for AffineMatrix {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
@@ -0,0 +1,56 @@
+/*
+ * Copyright 2026 Nathan Fiedler
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use crate::{GravityType, PixelWand};
+
+/// Settings for [`MagickWand::render_caption`](crate::MagickWand::render_caption)
+/// and [`MagickWand::render_label`](crate::MagickWand::render_label). Every
+/// field is optional; unset fields use ImageMagick's defaults.
+///
+/// When both `width` and `height` are given but `pointsize` is not,
+/// ImageMagick picks the largest point size at which the text fills the box.
+///
+/// ```no_run
+/// use magick_rust::{CaptionOptions, GravityType, MagickWand};
+///
+/// let card = MagickWand::render_caption(
+///     "A title of arbitrary length that wraps onto several lines",
+///     &CaptionOptions {
+///         width: Some(1000),
+///         height: Some(400),
+///         gravity: Some(GravityType::Center),
+///         ..Default::default()
+///     },
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CaptionOptions {
+    /// Width of the image in pixels; for captions, the width text wraps at.
+    pub width: Option<usize>,
+    /// Height of the image in pixels; when unset, the height of the text.
+    pub height: Option<usize>,
+    /// Font name or path to a font file.
+    pub font: Option<String>,
+    /// Font size in points.
+    pub pointsize: Option<f64>,
+    /// Placement of the text within the image.
+    pub gravity: Option<GravityType>,
+    /// Text color.
+    pub fill: Option<PixelWand>,
+    /// Background color of the image.
+    pub background: Option<PixelWand>,
+    /// Additional space between lines in pixels (may be negative).
+    pub interline_spacing: Option<f64>,
+}
@@ -0,0 +1,233 @@
+/*
+ * Copyright 2026 Nathan Fiedler
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::fs;
+use std::path::Path;
+
+use crate::result::{MagickError, Result};
+use crate::{PixelWand, QUANTUM_RANGE, bindings};
+
+/// An RGB color with alpha, each channel normalized to the range 0.0 to 1.0.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Color {
+    /// Red channel.
+    pub red: f64,
+    /// Green channel.
+    pub green: f64,
+    /// Blue channel.
+    pub blue: f64,
+    /// Opacity, where 0.0 is fully transparent and 1.0 fully opaque.
+    pub alpha: f64,
+}
+
+impl Color {
+    /// Create an opaque color from normalized channel values.
+    pub fn rgb(red: f64, green: f64, blue: f64) -> Self {
+        Color {
+            red,
+            green,
+            blue,
+            alpha: 1.0,
+        }
+    }
+
+    /// Read the color of a [`PixelWand`].
+    pub fn from_pixel_wand(pixel: &PixelWand) -> Self {
+        Color {
+            red: pixel.get_red(),
+            green: pixel.get_green(),
+            blue: pixel.get_blue(),
+            alpha: pixel.get_alpha(),
+        }
+    }
+
+    /// Create a [`PixelWand`] of this color.
+    pub fn to_pixel_wand(&self) -> PixelWand {
+        let mut pixel = PixelWand::new();
+        pixel.set_red(self.red);
+        pixel.set_green(self.green);
+        pixel.set_blue(self.blue);
+        pixel.set_alpha(self.alpha);
+        pixel
+    }
+
+    /// Perceptual difference between two colors, as the CIE76 distance (ΔE*ab)
+    /// of their CIELAB values. A difference of about 2.3 is just noticeable,
+    /// and black and white are 100 apart. Alpha is ignored.
+    pub fn perceptual_distance(&self, other: &Color) -> f64 {
+        let (l1, a1, b1) = self.to_lab();
+        let (l2, a2, b2) = other.to_lab();
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+    }
+
+    /// Reorder colors with their shares, such as the result of
+    /// [`MagickWand::dominant_colors`](crate::MagickWand::dominant_colors), so
+    /// that each color is the one most distinct from all colors before it. The
+    /// most common color comes first; the first few colors then make the most
+    /// varied palette, for example for swatches.
+    pub fn sort_by_distinctness(colors: &mut [(Color, f64)]) {
+        let Some(first) = (0..colors.len()).max_by(|&i, &j| colors[i].1.total_cmp(&colors[j].1))
+        else {
+            return;
+        };
+        colors.swap(0, first);
+        for sorted in 1..colors.len() {
+            let nearest = |candidate: &Color| {
+                colors[..sorted]
+                    .iter()
+                    .map(|(color, _)| candidate.perceptual_distance(color))
+                    .fold(f64::INFINITY, f64::min)
+            };
+            let distances: Vec<f64> = colors[sorted..]
+                .iter()
+                .map(|(color, _)| nearest(color))
+                .collect();
+            let farthest = (0..distances.len())
+                .max_by(|&i, &j| distances[i].total_cmp(&distances[j]))
+                .unwrap_or_default();
+            colors.swap(sorted, sorted + farthest);
+        }
+    }
+
+    /// Convert the sRGB color to CIELAB under the D65 white point.
+    fn to_lab(self) -> (f64, f64, f64) {
+        let linear = |value: f64| {
+            if value <= 0.04045 {
+                value / 12.92
+            } else {
+                ((value + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        let (red, green, blue) = (linear(self.red), linear(self.green), linear(self.blue));
+        let x = (0.4124 * red + 0.3576 * green + 0.1805 * blue) / 0.95047;
+        let y = 0.2126 * red + 0.7152 * green + 0.0722 * blue;
+        let z = (0.0193 * red + 0.1192 * green + 0.9505 * blue) / 1.08883;
+        let f = |t: f64| {
+            if t > 216.0 / 24389.0 {
+                t.cbrt()
+            } else {
+                (24389.0 / 27.0 * t + 16.0) / 116.0
+            }
+        };
+        let (fx, fy, fz) = (f(x), f(y), f(z));
+        (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+    }
+
+    /// Create an opaque color from 8-bit channel values.
+    fn from_rgb8(red: u8, green: u8, blue: u8) -> Self {
+        Color::rgb(
+            f64::from(red) / 255.0,
+            f64::from(green) / 255.0,
+            f64::from(blue) / 255.0,
+        )
+    }
+
+    /// Read the colors of a palette file, which is either a GIMP palette
+    /// (`.gpl`) or an Adobe Color Table (`.act`), chosen by the extension.
+    pub fn read_palette<P: AsRef<Path>>(path: P) -> Result<Vec<Color>> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        let read = |path: &Path| {
+            fs::read(path)
+                .map_err(|error| MagickError(format!("could not read {}: {error}", path.display())))
+        };
+        match extension.as_deref() {
+            Some("gpl") => Color::parse_gpl(&String::from_utf8_lossy(&read(path)?)),
+            Some("act") => Color::parse_act(&read(path)?),
+            _ => Err(MagickError(format!(
+                "unknown palette format: {}",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Parse a GIMP palette: a `GIMP Palette` header, optional `Name:` and
+    /// `Columns:` lines and `#` comments, then one `red green blue name` line
+    /// per color with 8-bit channel values.
+    pub fn parse_gpl(text: &str) -> Result<Vec<Color>> {
+        let mut lines = text.lines().enumerate();
+        if lines.next().map(|(_, line)| line.trim()) != Some("GIMP Palette") {
+            return Err(MagickError("missing GIMP Palette header".to_string()));
+        }
+        let mut colors = Vec::new();
+        for (index, line) in lines {
+            let line = line.trim();
+            if line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with("Name:")
+                || line.starts_with("Columns:")
+            {
+                continue;
+            }
+            let mut channels = line.split_whitespace().map(|value| value.parse::<u8>());
+            match (channels.next(), channels.next(), channels.next()) {
+                (Some(Ok(red)), Some(Ok(green)), Some(Ok(blue))) => {
+                    colors.push(Color::from_rgb8(red, green, blue))
+                }
+                _ => {
+                    return Err(MagickError(format!(
+                        "invalid GIMP palette color at line {}: {line}",
+                        index + 1
+                    )));
+                }
+            }
+        }
+        Ok(colors)
+    }
+
+    /// Parse an Adobe Color Table: 256 RGB triplets, optionally followed by
+    /// the number of colors in use and the index of the transparent color, both
+    /// big-endian 16-bit values.
+    pub fn parse_act(bytes: &[u8]) -> Result<Vec<Color>> {
+        let (count, transparent) = match bytes.len() {
+            768 => (256, None),
+            772 => {
+                let count = u16::from_be_bytes([bytes[768], bytes[769]]) as usize;
+                let transparent = u16::from_be_bytes([bytes[770], bytes[771]]) as usize;
+                (count.min(256), Some(transparent))
+            }
+            length => {
+                return Err(MagickError(format!(
+                    "invalid Adobe Color Table of {length} bytes"
+                )));
+            }
+        };
+        Ok(bytes[..count * 3]
+            .chunks_exact(3)
+            .enumerate()
+            .map(|(index, rgb)| Color {
+                alpha: if transparent == Some(index) { 0.0 } else { 1.0 },
+                ..Color::from_rgb8(rgb[0], rgb[1], rgb[2])
+            })
+            .collect())
+    }
+
+    /// Convert a `PixelInfo`, whose channels are in quantum units.
+    pub(crate) fn from_pixel_info(pixel: &bindings::PixelInfo) -> Self {
+        let alpha = match pixel.alpha_trait {
+            bindings::PixelTrait::Undefined => 1.0,
+            _ => pixel.alpha / QUANTUM_RANGE,
+        };
+        Color {
+            red: pixel.red / QUANTUM_RANGE,
+            green: pixel.green / QUANTUM_RANGE,
+            blue: pixel.blue / QUANTUM_RANGE,
+            alpha,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+/*
+ * Copyright 2026 Nathan Fiedler
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use crate::bindings;
+use crate::types::Color;
+
+/// A connected region of similar pixels found by
+/// [`MagickWand::connected_components_image`](crate::MagickWand::connected_components_image).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Component {
+    /// Label of the component, which is also its pixel value in the labeled
+    /// image.
+    pub id: isize,
+    /// Bounding box of the component as `(x, y, width, height)`.
+    pub bbox: (isize, isize, usize, usize),
+    /// Center of mass `(x, y)` of the component.
+    pub centroid: (f64, f64),
+    /// Number of pixels in the component.
+    pub area: f64,
+    /// Mean color of the component's pixels in the original image.
+    pub mean_color: Color,
+}
+
+impl Component {
+    /// Convert an object computed by `ConnectedComponentsImage`.
+    pub(crate) fn from_object(object: &bindings::CCObjectInfo) -> Self {
+        let bbox = &object.bounding_box;
+        Component {
+            id: object.id,
+            bbox: (bbox.x, bbox.y, bbox.width, bbox.height),
+            centroid: (object.centroid.x, object.centroid.y),
+            area: object.area,
+            mean_color: Color::from_pixel_info(&object.color),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+/*
+ * Copyright 2026 Nathan Fiedler
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use crate::bindings;
+
+/// Measurements of a piece of text rendered with a given font and point size,
+/// as returned by [`MagickWand::query_font_metrics`](crate::MagickWand::query_font_metrics)
+/// and [`DrawingWand::get_type_metrics`](crate::DrawingWand::get_type_metrics).
+///
+/// All values are in pixels. The `descender` is negative for fonts whose
+/// glyphs extend below the baseline.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FontMetrics {
+    /// Width of a character cell (the font's pixels per em, horizontally).
+    pub character_width: f64,
+    /// Height of a character cell (the font's pixels per em, vertically).
+    pub character_height: f64,
+    /// Distance from the baseline to the top of the tallest glyph.
+    pub ascender: f64,
+    /// Distance from the baseline to the bottom of the lowest glyph.
+    pub descender: f64,
+    /// Width of the rendered text.
+    pub text_width: f64,
+    /// Height of the rendered text (for multiple lines, of all lines).
+    pub text_height: f64,
+    /// Maximum horizontal advance of any glyph.
+    pub max_horizontal_advance: f64,
+    /// Lower left corner `(x1, y1)` of the glyph bounding box, relative to the
+    /// origin.
+    pub bounds_min: (f64, f64),
+    /// Upper right corner `(x2, y2)` of the glyph bounding box, relative to the
+    /// origin.
+    pub bounds_max: (f64, f64),
+    /// Position `(x, y)` where the next character would be drawn.
+    pub origin: (f64, f64),
+}
+
+impl FontMetrics {
+    /// Convert the 13 values returned by `MagickQueryFontMetrics`.
+    pub(crate) fn from_slice(values: &[f64]) -> Self {
+        FontMetrics {
+            character_width: values[0],
+            character_height: values[1],
+            ascender: values[2],
+            descender: values[3],
+            text_width: values[4],
+            text_height: values[5],
+            max_horizontal_advance: values[6],
+            bounds_min: (values[7], values[8]),
+            bounds_max: (values[9], values[10]),
+            origin: (values[11], values[12]),
+        }
+    }
+
+    /// Convert the `TypeMetric` filled in by `DrawGetTypeMetrics`.
+    pub(crate) fn from_type_metric(metric: &bindings::TypeMetric) -> Self {
+        FontMetrics {
+            character_width: metric.pixels_per_em.x,
+            character_height: metric.pixels_per_em.y,
+            ascender: metric.ascent,
+            descender: metric.descent,
+            text_width: metric.width,
+            text_height: metric.height,
+            max_horizontal_advance: metric.max_advance,
+            bounds_min: (metric.bounds.x1, metric.bounds.y1),
+            bounds_max: (metric.bounds.x2, metric.bounds.y2),
+            origin: (metric.origin.x, metric.origin.y),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+/*
+ * Copyright 2026 Nathan Fiedler
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/// A straight line found by
+/// [`MagickWand::hough_line_image`](crate::MagickWand::hough_line_image).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct HoughLine {
+    /// One end `(x, y)` of the line, where it meets the image border.
+    pub start: (f64, f64),
+    /// The other end `(x, y)` of the line.
+    pub end: (f64, f64),
+    /// Number of edge pixels that voted for the line in the accumulator.
+    pub votes: usize,
+    /// Angle of the line's normal in degrees.
+    pub angle: f64,
+    /// Distance of the line from the image origin, in pixels.
+    pub distance: f64,
+}
+
+impl HoughLine {
+    /// Parse the MVG that `HoughLineImage` renders the lines from, which has
+    /// one `line x1,y1 x2,y2  # count angle distance` command per line.
+    pub(crate) fn parse_mvg(mvg: &str) -> Vec<HoughLine> {
+        mvg.lines()
+            .filter_map(|line| {
+                let (command, comment) = line.split_once('#').unwrap_or((line, ""));
+                let mut coordinates = command
+                    .trim()
+                    .strip_prefix("line")?
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|value| !value.is_empty())
+                    .map(|value| value.parse::<f64>().ok());
+                let mut next = || coordinates.next().flatten();
+                let start = (next()?, next()?);
+                let end = (next()?, next()?);
+                let mut statistics = comment
+                    .split_whitespace()
+                    .map(|value| value.parse::<f64>().unwrap_or_default());
+                let mut next = || statistics.next().unwrap_or_default();
+                Some(HoughLine {
+                    start,
+                    end,
+                    votes: next() as usize,
+                    angle: next(),
+                    distance: next(),
+                })
+            })
+            .collect()
+    }
+}
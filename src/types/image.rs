@@ -56,9 +56,10 @@ impl Image<'_> {
 ///
 /// This is a `&self` counterpart to [`MagickWand::set_iterator_index`] (which
 /// takes `&mut self`); the shared borrow is sound because it mutates only the
-/// iterator cursor in C-allocated memory behind the wand pointer. `index` is
-/// always validated against the image count before this is called, so the
-/// boolean return is ignored.
+/// iterator cursor in C-allocated memory behind the wand pointer, and
+/// `MagickWand` is not `Sync`, so no other thread can observe the cursor move.
+/// `index` is always validated against the image count before this is called,
+/// so the boolean return is ignored.
 fn pin(wand: &MagickWand, index: isize) {
     debug_assert!(
         index >= 0 && (index as usize) < wand.get_number_images(),
@@ -83,7 +84,9 @@ pub struct Images<'w> {
 
 impl<'w> Images<'w> {
     pub(crate) fn new(wand: &'w MagickWand) -> Self {
-        wand.reset_iterator();
+        // Like `pin`, this only moves the iterator cursor, so the shared
+        // borrow is enough.
+        unsafe { bindings::MagickResetIterator(wand.as_ptr()) };
         Images { wand }
     }
 
@@ -146,8 +149,88 @@ impl<'w> Images<'w> {
         }
         Ok(())
     }
+
+    /// Iterate over the frames in order. The iterator is double-ended and
+    /// knows its exact length, so `rev()`, `len()` and friends all work.
+    pub fn iter(&self) -> ImagesIter<'w> {
+        ImagesIter {
+            wand: self.wand,
+            front: 0,
+            back: self.count(),
+        }
+    }
+}
+
+impl<'w> IntoIterator for &Images<'w> {
+    type Item = ImageRef<'w>;
+    type IntoIter = ImagesIter<'w>;
+
+    fn into_iter(self) -> ImagesIter<'w> {
+        self.iter()
+    }
+}
+
+impl<'w> IntoIterator for Images<'w> {
+    type Item = ImageRef<'w>;
+    type IntoIter = ImagesIter<'w>;
+
+    fn into_iter(self) -> ImagesIter<'w> {
+        self.iter()
+    }
+}
+
+/// An iterator over the frames of an [`Images`] view, yielding [`ImageRef`]
+/// handles.
+///
+/// Created by [`Images::iter`] or by iterating `&Images`. The set of indices
+/// is fixed when the iterator is created; since the view borrows the wand
+/// immutably, the frame count cannot change while it is alive.
+pub struct ImagesIter<'w> {
+    wand: &'w MagickWand,
+    front: usize,
+    back: usize,
 }
 
+impl<'w> Iterator for ImagesIter<'w> {
+    type Item = ImageRef<'w>;
+
+    fn next(&mut self) -> Option<ImageRef<'w>> {
+        if self.front < self.back {
+            let index = self.front;
+            self.front += 1;
+            Some(ImageRef {
+                wand: self.wand,
+                index: index as isize,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for ImagesIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(ImageRef {
+                wand: self.wand,
+                index: self.back as isize,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for ImagesIter<'_> {}
+
+impl std::iter::FusedIterator for ImagesIter<'_> {}
+
 /// A mutable view over the images (frames) held by a [`MagickWand`].
 ///
 /// Obtained from [`MagickWand::images_mut`]. Because ImageMagick exposes a
@@ -217,6 +300,104 @@ impl<'w> ImagesMut<'w> {
         self.wand.add_image(other)
     }
 
+    /// Insert all frames from `other` so that the first of them ends up at
+    /// `index`, shifting the frame previously at `index` (and every later
+    /// frame) towards the end. An `index` equal to the frame count appends.
+    pub fn insert(&mut self, index: usize, other: &MagickWand) -> Result<()> {
+        let count = self.count();
+        if index > count {
+            return Err(MagickError(format!(
+                "image index {index} out of bounds (count {count})"
+            )));
+        }
+        if index == 0 {
+            // MagickAddImage prepends when the iterator is set to the first
+            // image (and simply adds to an empty wand).
+            self.wand.set_first_iterator();
+        } else {
+            // Otherwise the new frames are inserted just after the current one.
+            pin(self.wand, index as isize - 1);
+        }
+        self.wand.add_image(other)
+    }
+
+    /// Swap the frames at indices `a` and `b`.
+    pub fn swap(&mut self, a: usize, b: usize) -> Result<()> {
+        let count = self.count();
+        if a >= count || b >= count {
+            return Err(MagickError(format!(
+                "image index {} out of bounds (count {count})",
+                a.max(b)
+            )));
+        }
+        let (low, high) = if a < b { (a, b) } else { (b, a) };
+        if low == high {
+            return Ok(());
+        }
+        let low_frame = self.extract(low)?;
+        let high_frame = self.extract(high)?;
+        // Replace the higher index first so the lower one stays valid.
+        self.remove(high)?;
+        self.insert(high, &low_frame)?;
+        self.remove(low)?;
+        self.insert(low, &high_frame)
+    }
+
+    /// Move the frame at `from` so that it ends up at index `to`, shifting the
+    /// frames in between by one position.
+    pub fn move_frame(&mut self, from: usize, to: usize) -> Result<()> {
+        let count = self.count();
+        if from >= count || to >= count {
+            return Err(MagickError(format!(
+                "image index {} out of bounds (count {count})",
+                from.max(to)
+            )));
+        }
+        if from == to {
+            return Ok(());
+        }
+        let frame = self.extract(from)?;
+        self.remove(from)?;
+        self.insert(to, &frame)
+    }
+
+    /// Reverse the order of the frames in the list.
+    pub fn reverse(&mut self) -> Result<()> {
+        let count = self.count();
+        // Repeatedly move the last frame forward: after step `i`, the first
+        // `i + 1` frames hold the original last `i + 1` frames in reverse.
+        for index in 0..count.saturating_sub(1) {
+            self.move_frame(count - 1, index)?;
+        }
+        Ok(())
+    }
+
+    /// Retain only the frames for which `f` returns `true`, removing the rest.
+    /// The relative order of the retained frames is preserved.
+    pub fn retain(&mut self, mut f: impl FnMut(ImageRef<'_>) -> bool) -> Result<()> {
+        let doomed: Vec<usize> = (0..self.count())
+            .filter(|&index| {
+                !f(ImageRef {
+                    wand: &*self.wand,
+                    index: index as isize,
+                })
+            })
+            .collect();
+        // Remove from the back so the remaining indices stay valid.
+        for index in doomed.into_iter().rev() {
+            self.remove(index)?;
+        }
+        Ok(())
+    }
+
+    /// Clone the frame at `index` into a new single-image wand, carrying over
+    /// its per-frame settings (delay, disposal, page geometry, etc.).
+    fn extract(&mut self, index: usize) -> Result<MagickWand> {
+        pin(self.wand, index as isize);
+        let image = self.wand.get_image()?;
+        MagickWand::new_from_image(&image)
+    }
+
     /// Visit every frame in order, passing its index and a mutable borrow to
     /// `f`.
     ///
@@ -14,18 +14,33 @@
  * limitations under the License.
  */
 
+mod affine_matrix;
+mod caption_options;
+mod color;
+mod component;
+mod font_metrics;
 mod geometry_info;
+mod hough_line;
 mod image;
 mod kernel;
+mod threshold_map;
 
+pub use self::affine_matrix::AffineMatrix;
+pub use self::caption_options::CaptionOptions;
+pub use self::color::Color;
+pub use self::component::Component;
+pub use self::font_metrics::FontMetrics;
 pub use self::geometry_info::GeometryInfo;
-pub use self::image::{Image, ImageMut, ImageRef, Images, ImagesMut};
+pub use self::hough_line::HoughLine;
+pub use self::image::{Image, ImageMut, ImageRef, Images, ImagesIter, ImagesMut};
 pub use self::kernel::{KernelBuilder, KernelInfo};
+pub use self::threshold_map::ThresholdMap;
 /// Text alignment for drawing operations (Left, Center, Right).
 pub use crate::bindings::AlignType;
 /// How an image's alpha (transparency) channel is activated or modified.
 pub use crate::bindings::AlphaChannelOption;
 /// Algorithm used to automatically choose a thresholding value (Kapur, OTSU, Triangle).
+#[cfg(magick_7_0_8_0)]
 pub use crate::bindings::AutoThresholdMethod;
 /// Selects which image channels an operation applies to (Red, Green, Blue, Alpha, etc.).
 pub use crate::bindings::ChannelType;
@@ -33,6 +48,8 @@ pub use crate::bindings::ChannelType;
 pub use crate::bindings::ClipPathUnits;
 /// The colorspace of an image (RGB, sRGB, CMYK, Gray, etc.).
 pub use crate::bindings::ColorspaceType;
+/// Arithmetic applied by `complex_images()` to pairs of real and imaginary images (Add, Multiply, etc.).
+pub use crate::bindings::ComplexOperator;
 /// How a source image is combined with a destination image when compositing (Over, Multiply, etc.).
 pub use crate::bindings::CompositeOperator;
 /// Compression algorithm used when encoding an image (LZW, JPEG, Zip, etc.).
@@ -73,6 +90,8 @@ pub use crate::bindings::MagickFunction;
 pub use crate::bindings::MetricType;
 /// Morphology operation applied with a kernel (Erode, Dilate, Open, Close, etc.).
 pub use crate::bindings::MorphologyMethod;
+/// Distribution of the noise added by `add_noise_image()` (Uniform, Gaussian, Poisson, etc.).
+pub use crate::bindings::NoiseType;
 /// The EXIF-style orientation of an image (TopLeft, BottomRight, etc.).
 pub use crate::bindings::OrientationType;
 /// How a flood-fill or paint operation matches neighboring pixels (Point, Replace, Floodfill, etc.).
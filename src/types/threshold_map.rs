@@ -0,0 +1,71 @@
+/*
+ * Copyright 2026 Nathan Fiedler
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/// A threshold map for ordered dithering, as returned by
+/// [`magick_threshold_maps`](crate::magick_threshold_maps). Either the name
+/// or the alias can be passed to
+/// [`MagickWand::ordered_dither_image`](crate::MagickWand::ordered_dither_image).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThresholdMap {
+    /// Name of the map, such as `o4x4`.
+    pub name: String,
+    /// Alternative name of the map, such as `bayer4x4`.
+    pub alias: Option<String>,
+    /// Description of the map, such as `Ordered 4x4 (dispersed)`.
+    pub description: String,
+}
+
+impl ThresholdMap {
+    /// Parse the table written by ImageMagick's `ListThresholdMaps`: for each
+    /// `thresholds.xml` file a header line naming the `Map`, `Alias` and
+    /// `Description` columns, a line of dashes, and one line per map.
+    #[cfg(unix)]
+    pub(crate) fn parse_list(listing: &str) -> Vec<ThresholdMap> {
+        let mut maps = Vec::new();
+        let mut columns = None;
+        for line in listing.lines() {
+            if line.starts_with("Map") {
+                columns = line
+                    .find("Alias")
+                    .zip(line.find("Description"))
+                    .filter(|(alias, description)| alias < description);
+                continue;
+            }
+            let Some((alias_column, description_column)) = columns else {
+                continue;
+            };
+            if line.trim().is_empty() || line.starts_with('-') || line.starts_with("Path:") {
+                continue;
+            }
+            let column = |start: usize, end: usize| {
+                line.get(start.min(line.len())..end.min(line.len()))
+                    .unwrap_or_default()
+                    .trim()
+            };
+            let name = column(0, alias_column);
+            if name.is_empty() {
+                continue;
+            }
+            let alias = column(alias_column, description_column);
+            maps.push(ThresholdMap {
+                name: name.to_string(),
+                alias: (!alias.is_empty()).then(|| alias.to_string()),
+                description: column(description_column, line.len()).to_string(),
+            });
+        }
+        maps
+    }
+}
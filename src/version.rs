@@ -0,0 +1,193 @@
+/*
+ * Copyright 2026 Nathan Fiedler
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::ffi::CStr;
+
+use libc::c_char;
+
+use crate::bindings;
+use crate::quantum::{HDRI_ENABLED, QUANTUM_DEPTH};
+use crate::result::{MagickError, Result};
+
+/// Version, build configuration, and delegate libraries of the linked
+/// ImageMagick, as returned by [`magick_version`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MagickVersion {
+    /// Major version, e.g. `7` for 7.1.1-47.
+    pub major: u32,
+    /// Minor version, e.g. `1` for 7.1.1-47.
+    pub minor: u32,
+    /// Micro version, e.g. `1` for 7.1.1-47.
+    pub micro: u32,
+    /// Patch level, e.g. `47` for 7.1.1-47.
+    pub patch: u32,
+    /// The full version string, e.g.
+    /// `ImageMagick 7.1.1-47 Q16-HDRI x86_64 22763 https://imagemagick.org`.
+    pub description: String,
+    /// Bits per pixel component (8, 16, 32, or 64).
+    pub quantum_depth: usize,
+    /// Whether the library was built with high dynamic range imaging, where
+    /// pixel components are floating point values.
+    pub hdri: bool,
+    /// Optional features compiled in, as reported by ImageMagick, e.g.
+    /// `["Cipher", "DPC", "HDRI", "OpenMP(4.5)"]`.
+    pub features: Vec<String>,
+    /// Delegate libraries compiled in, e.g. `["jpeg", "png", "webp", ...]`.
+    pub delegates: Vec<String>,
+}
+
+impl MagickVersion {
+    /// The version as a `(major, minor, micro, patch)` tuple, handy for
+    /// comparisons such as `version.as_tuple() >= (7, 1, 1, 0)`.
+    pub fn as_tuple(&self) -> (u32, u32, u32, u32) {
+        (self.major, self.minor, self.micro, self.patch)
+    }
+
+    /// Returns `true` if the named feature is enabled. The comparison ignores
+    /// case and any parenthesized detail, so `"openmp"` matches `OpenMP(4.5)`.
+    pub fn has_feature(&self, name: &str) -> bool {
+        self.features.iter().any(|feature| {
+            let base = feature.split('(').next().unwrap_or(feature);
+            base.eq_ignore_ascii_case(name)
+        })
+    }
+
+    /// Returns `true` if the named delegate library (e.g. `"webp"`) is
+    /// available, ignoring case.
+    pub fn has_delegate(&self, name: &str) -> bool {
+        self.delegates
+            .iter()
+            .any(|delegate| delegate.eq_ignore_ascii_case(name))
+    }
+
+    /// Whether ImageMagick was built with OpenMP multi-threading.
+    pub fn openmp(&self) -> bool {
+        self.has_feature("OpenMP")
+    }
+
+    /// Whether ImageMagick was built with OpenCL acceleration.
+    pub fn opencl(&self) -> bool {
+        self.has_feature("OpenCL")
+    }
+
+    /// Whether ImageMagick was built with the encipher/decipher support.
+    pub fn cipher(&self) -> bool {
+        self.has_feature("Cipher")
+    }
+
+    /// Whether ImageMagick was built with the FFTW delegate, which the Fourier
+    /// transform operations require.
+    pub fn fftw(&self) -> bool {
+        self.has_delegate("fftw")
+    }
+
+    /// Whether ImageMagick was built for zero configuration (no external
+    /// configuration files).
+    pub fn zero_configuration(&self) -> bool {
+        self.has_feature("Zero-configuration")
+    }
+
+    /// Fail with an error naming every delegate in `names` that is missing,
+    /// e.g. as a startup check that a container can actually decode WebP.
+    pub fn require_delegates(&self, names: &[&str]) -> Result<()> {
+        let missing: Vec<&str> = names
+            .iter()
+            .copied()
+            .filter(|name| !self.has_delegate(name))
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(MagickError(format!(
+                "ImageMagick is missing delegates: {}",
+                missing.join(", ")
+            )))
+        }
+    }
+
+    /// Check that the linked library matches the quantum depth and HDRI setting
+    /// of the headers the bindings were generated from. A mismatch, such as
+    /// building against a Q16 HDRI install but running with a non-HDRI library,
+    /// makes every quantum value returned by the crate wrong.
+    pub fn verify_build_config(&self) -> Result<()> {
+        let depth = QUANTUM_DEPTH;
+        let hdri = HDRI_ENABLED;
+        if self.quantum_depth != depth || self.hdri != hdri {
+            return Err(MagickError(format!(
+                "ImageMagick library is Q{}{} but magick_rust was built for Q{}{}",
+                self.quantum_depth,
+                if self.hdri { "-HDRI" } else { "" },
+                depth,
+                if hdri { "-HDRI" } else { "" },
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn static_string(ptr: *const c_char) -> String {
+    if ptr.is_null() {
+        String::new()
+    } else {
+        unsafe { CStr::from_ptr(ptr) }
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+fn split_words(text: &str) -> Vec<String> {
+    text.split_whitespace().map(str::to_owned).collect()
+}
+
+/// Parse the `7.1.1-47` part of the version description.
+fn parse_version(description: &str) -> Option<(u32, u32, u32, u32)> {
+    let number = description.split_whitespace().nth(1)?;
+    let (release, patch) = number.split_once('-').unwrap_or((number, "0"));
+    let mut parts = release.split('.').map(|part| part.parse::<u32>().ok());
+    let major = parts.next()??;
+    let minor = parts.next()??;
+    let micro = parts.next().flatten().unwrap_or(0);
+    Some((major, minor, micro, patch.parse().ok()?))
+}
+
+/// Return the version, quantum depth, HDRI flag, enabled features, and
+/// delegate libraries of the ImageMagick library the program is linked with,
+/// using `MagickGetVersion`, `GetMagickQuantumDepth`, `GetMagickFeatures`, and
+/// `GetMagickDelegates`.
+pub fn magick_version() -> Result<MagickVersion> {
+    let mut version_number = 0;
+    let description = static_string(unsafe { bindings::MagickGetVersion(&mut version_number) });
+    let (major, minor, micro, patch) = parse_version(&description).ok_or_else(|| {
+        MagickError(format!(
+            "could not parse ImageMagick version: {description}"
+        ))
+    })?;
+    let mut quantum_depth = 0;
+    unsafe { bindings::GetMagickQuantumDepth(&mut quantum_depth) };
+    let features = split_words(&static_string(unsafe { bindings::GetMagickFeatures() }));
+    let delegates = split_words(&static_string(unsafe { bindings::GetMagickDelegates() }));
+    let hdri = features.iter().any(|feature| feature == "HDRI");
+    Ok(MagickVersion {
+        major,
+        minor,
+        micro,
+        patch,
+        description,
+        quantum_depth,
+        hdri,
+        features,
+        delegates,
+    })
+}
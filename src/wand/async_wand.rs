@@ -0,0 +1,319 @@
+/*
+ * Copyright 2026 Nathan Fiedler
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use libc::{c_char, c_void};
+use tokio::sync::Semaphore;
+
+use crate::bindings;
+use crate::result::MagickError;
+use crate::result::Result;
+use crate::{FilterType, MagickWand, ResourceType};
+
+/// Cancellation flags of the operations currently running on the blocking
+/// pool, keyed by the id handed to ImageMagick as the progress monitor's client
+/// data. Going through this table (rather than handing ImageMagick a pointer to
+/// the flag) means a monitor that outlives its operation, e.g. on an image that
+/// was cloned into another wand, can never dereference freed memory.
+static CANCEL_FLAGS: OnceLock<Mutex<HashMap<usize, Arc<AtomicBool>>>> = OnceLock::new();
+static NEXT_TOKEN_ID: AtomicUsize = AtomicUsize::new(1);
+static DEFAULT_LIMITER: OnceLock<Arc<Semaphore>> = OnceLock::new();
+
+fn cancel_flags() -> &'static Mutex<HashMap<usize, Arc<AtomicBool>>> {
+    CANCEL_FLAGS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Progress monitor installed while an operation runs; returning false makes
+/// ImageMagick abandon the operation as soon as it next reports progress.
+unsafe extern "C" fn cancellation_monitor(
+    _text: *const c_char,
+    _offset: bindings::MagickOffsetType,
+    _span: bindings::MagickSizeType,
+    client_data: *mut c_void,
+) -> bindings::MagickBooleanType {
+    let cancelled = cancel_flags()
+        .lock()
+        .ok()
+        .and_then(|flags| flags.get(&(client_data as usize)).cloned())
+        .is_some_and(|flag| flag.load(Ordering::Relaxed));
+    (!cancelled).into()
+}
+
+/// Install (or, with `None`, remove) the progress monitor on the wand, which
+/// covers images read later, and on every image it already holds.
+fn set_progress_monitors(
+    wand: &mut MagickWand,
+    monitor: bindings::MagickProgressMonitor,
+    client_data: usize,
+) {
+    let client_data = client_data as *mut c_void;
+    unsafe {
+        bindings::MagickSetProgressMonitor(wand.as_ptr(), monitor, client_data);
+    }
+    let count = wand.get_number_images();
+    if count == 0 {
+        return;
+    }
+    let current = wand.get_iterator_index();
+    for index in 0..count {
+        unsafe {
+            bindings::MagickSetIteratorIndex(wand.as_ptr(), index as isize);
+            bindings::MagickSetImageProgressMonitor(wand.as_ptr(), monitor, client_data);
+        }
+    }
+    unsafe {
+        bindings::MagickSetIteratorIndex(wand.as_ptr(), current.max(0));
+    }
+}
+
+/// The concurrency limiter shared by every [`AsyncMagickWand`] created with
+/// [`AsyncMagickWand::new`].
+///
+/// It is created on first use with as many permits as the ImageMagick `Thread`
+/// resource limit (see [`MagickWand::get_resource_limit`]), so that the number
+/// of wand operations running at once on the blocking pool matches what
+/// ImageMagick itself was configured to use. Set the resource limit before the
+/// first async operation to change it, or pass a semaphore of your own to
+/// [`AsyncMagickWand::with_limiter`].
+pub fn default_limiter() -> Arc<Semaphore> {
+    DEFAULT_LIMITER
+        .get_or_init(|| {
+            let threads = MagickWand::get_resource_limit(ResourceType::Thread) as usize;
+            Arc::new(Semaphore::new(threads.clamp(1, Semaphore::MAX_PERMITS)))
+        })
+        .clone()
+}
+
+/// A handle for cancelling the operations of an [`AsyncMagickWand`], obtained
+/// from [`AsyncMagickWand::cancellation_token`].
+///
+/// Cancelling is cooperative: the running operation stops the next time it
+/// reports progress to ImageMagick's progress monitor (most pixel operations do
+/// so once per row) and fails with an error. Operations that never report
+/// progress run to completion. Once cancelled, further operations fail until
+/// [`AsyncMagickWand::reset_cancellation`] is called.
+#[derive(Clone, Debug)]
+pub struct CancellationToken {
+    id: usize,
+    flag: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    fn new() -> Self {
+        CancellationToken {
+            id: NEXT_TOKEN_ID.fetch_add(1, Ordering::Relaxed),
+            flag: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Request cancellation of the current and any later operations.
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if cancellation has been requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+}
+
+/// Registers the token with the progress monitor for as long as an operation
+/// runs. If the awaiting future is dropped this also cancels the token, so the
+/// abandoned blocking task stops early instead of finishing unobserved.
+struct OperationGuard {
+    token: CancellationToken,
+    finished: bool,
+}
+
+impl OperationGuard {
+    fn new(token: &CancellationToken) -> Self {
+        if let Ok(mut flags) = cancel_flags().lock() {
+            flags.insert(token.id, token.flag.clone());
+        }
+        OperationGuard {
+            token: token.clone(),
+            finished: false,
+        }
+    }
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.token.cancel();
+        }
+    }
+}
+
+/// A `MagickWand` whose operations run on the tokio blocking thread pool.
+///
+/// Reading, processing, and encoding images are CPU bound and would stall an
+/// async executor, so each operation is moved to [`tokio::task::spawn_blocking`]
+/// after acquiring a permit from a concurrency limiter (see
+/// [`default_limiter`]). The wand moves to the blocking thread for the duration
+/// of the operation and is returned afterwards.
+///
+/// If the future of an operation is dropped before it completes, the operation
+/// is cancelled (see [`CancellationToken`]) and the wand is lost along with the
+/// abandoned task; later operations fail with an error.
+///
+/// Requires the `tokio` feature.
+///
+/// ```no_run
+/// use magick_rust::{AsyncMagickWand, FilterType, MagickWand};
+///
+/// async fn thumbnail(data: Vec<u8>) -> Result<Vec<u8>, magick_rust::MagickError> {
+///     let mut wand = AsyncMagickWand::new(MagickWand::new());
+///     wand.read_image_blob(data).await?;
+///     wand.resize_image(128, 128, FilterType::Lanczos).await?;
+///     wand.write_image_blob("webp").await
+/// }
+/// ```
+pub struct AsyncMagickWand {
+    wand: Option<MagickWand>,
+    limiter: Arc<Semaphore>,
+    token: CancellationToken,
+}
+
+impl AsyncMagickWand {
+    /// Wrap the wand, limiting concurrency with the [`default_limiter`].
+    pub fn new(wand: MagickWand) -> Self {
+        Self::with_limiter(wand, default_limiter())
+    }
+
+    /// Wrap the wand, limiting concurrency with the given semaphore. Each
+    /// operation holds one permit while it runs.
+    pub fn with_limiter(wand: MagickWand, limiter: Arc<Semaphore>) -> Self {
+        AsyncMagickWand {
+            wand: Some(wand),
+            limiter,
+            token: CancellationToken::new(),
+        }
+    }
+
+    /// Returns a token that can cancel this wand's operations from elsewhere.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    /// Replace a cancelled token with a fresh one so the wand can be used
+    /// again. Tokens handed out earlier no longer affect this wand.
+    pub fn reset_cancellation(&mut self) {
+        self.token = CancellationToken::new();
+    }
+
+    /// Unwrap the underlying wand, or fail if it was lost to a cancelled
+    /// operation.
+    pub fn into_inner(mut self) -> Result<MagickWand> {
+        self.wand.take().ok_or_else(Self::lost)
+    }
+
+    fn lost() -> MagickError {
+        MagickError("wand was lost to a cancelled operation".to_string())
+    }
+
+    /// Run `f` with the wand on the blocking thread pool and return its result.
+    /// This is the building block for the convenience methods below, and
+    /// gives access to every `MagickWand` operation.
+    pub async fn run<F, T>(&mut self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut MagickWand) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        if self.token.is_cancelled() {
+            return Err(MagickError("operation cancelled".to_string()));
+        }
+        let permit = self
+            .limiter
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|_| "concurrency limiter was closed")?;
+        let mut wand = self.wand.take().ok_or_else(Self::lost)?;
+        let mut guard = OperationGuard::new(&self.token);
+        let id = self.token.id;
+        let flag = self.token.flag.clone();
+        let task = tokio::task::spawn_blocking(move || {
+            set_progress_monitors(&mut wand, Some(cancellation_monitor), id);
+            let result = f(&mut wand);
+            set_progress_monitors(&mut wand, None, 0);
+            if let Ok(mut flags) = cancel_flags().lock() {
+                flags.remove(&id);
+            }
+            drop(permit);
+            let result = match result {
+                Err(_) if flag.load(Ordering::Relaxed) => {
+                    Err(MagickError("operation cancelled".to_string()))
+                }
+                result => result,
+            };
+            (wand, result)
+        });
+        let joined = task.await;
+        guard.finished = true;
+        match joined {
+            Ok((wand, result)) => {
+                self.wand = Some(wand);
+                result
+            }
+            Err(err) => Err(MagickError(format!("blocking task failed: {err}"))),
+        }
+    }
+
+    /// Read the image data from the bytes; see [`MagickWand::read_image_blob`].
+    pub async fn read_image_blob(&mut self, data: Vec<u8>) -> Result<()> {
+        self.run(move |wand| wand.read_image_blob(data)).await
+    }
+
+    /// Read the named image file; see [`MagickWand::read_image`].
+    pub async fn read_image(&mut self, path: &str) -> Result<()> {
+        let path = path.to_owned();
+        self.run(move |wand| wand.read_image(&path)).await
+    }
+
+    /// Resize the image; see [`MagickWand::resize_image`].
+    pub async fn resize_image(
+        &mut self,
+        width: usize,
+        height: usize,
+        filter: FilterType,
+    ) -> Result<()> {
+        self.run(move |wand| wand.resize_image(width, height, filter))
+            .await
+    }
+
+    /// Encode the image in the given format; see
+    /// [`MagickWand::write_image_blob`].
+    pub async fn write_image_blob(&mut self, format: &str) -> Result<Vec<u8>> {
+        let format = format.to_owned();
+        self.run(move |wand| wand.write_image_blob(&format)).await
+    }
+
+    /// Encode all images in the given format; see
+    /// [`MagickWand::write_images_blob`].
+    pub async fn write_images_blob(&mut self, format: &str) -> Result<Vec<u8>> {
+        let format = format.to_owned();
+        self.run(move |wand| wand.write_images_blob(&format)).await
+    }
+}
+
+impl From<MagickWand> for AsyncMagickWand {
+    fn from(wand: MagickWand) -> Self {
+        AsyncMagickWand::new(wand)
+    }
+}
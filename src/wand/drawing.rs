@@ -14,15 +14,19 @@
  * limitations under the License.
  */
 use std::ffi::{CStr, CString};
-use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::{fmt, ptr, slice};
+
+use libc::c_void;
 
 use crate::bindings;
 
+use super::{MagickWand, PixelWand};
 use crate::result::MagickError;
 use crate::result::Result;
 use crate::{
-    AlignType, ClipPathUnits, DecorationType, DirectionType, FillRule, GravityType, LineCap,
-    LineJoin, PaintMethod, StretchType, StyleType,
+    AffineMatrix, AlignType, ClipPathUnits, CompositeOperator, DecorationType, DirectionType,
+    FillRule, GravityType, LineCap, LineJoin, PaintMethod, StretchType, StyleType,
 };
 
 wand_common!(
@@ -177,6 +181,361 @@ impl DrawingWand {
         }
     }
 
+    /// Save the current drawing settings (colors, font, transformation, clip
+    /// path, ...) on the graphic context stack.
+    pub fn push_graphic_context(&mut self) -> Result<()> {
+        self.result_from_boolean(unsafe { bindings::PushDrawingWand(self.wand) })
+    }
+
+    /// Restore the drawing settings saved by the matching
+    /// [`push_graphic_context`](Self::push_graphic_context).
+    pub fn pop_graphic_context(&mut self) -> Result<()> {
+        self.result_from_boolean(unsafe { bindings::PopDrawingWand(self.wand) })
+    }
+
+    /// Push the graphic context and return a guard that pops it again when
+    /// dropped. Settings changed and transformations applied through the guard
+    /// only affect what is drawn while it is alive.
+    ///
+    /// ```no_run
+    /// use magick_rust::DrawingWand;
+    ///
+    /// let mut drawing = DrawingWand::new();
+    /// {
+    ///     let mut rotated = drawing.push_context().unwrap();
+    ///     rotated.translate(50.0, 50.0);
+    ///     rotated.rotate(-30.0);
+    ///     rotated.draw_annotation(0.0, 0.0, "rotated label").unwrap();
+    /// }
+    /// drawing.draw_annotation(10.0, 90.0, "upright label").unwrap();
+    /// ```
+    pub fn push_context(&mut self) -> Result<GraphicContext<'_>> {
+        self.push_graphic_context()?;
+        Ok(GraphicContext { drawing_wand: self })
+    }
+
+    /// Apply the affine transformation to the coordinate system of everything
+    /// drawn afterwards, on top of the current transformation.
+    pub fn affine(&mut self, matrix: &AffineMatrix) {
+        let raw = matrix.to_raw();
+        unsafe { bindings::DrawAffine(self.wand, &raw) }
+    }
+
+    /// Move the origin of the coordinate system to `(x, y)`.
+    pub fn translate(&mut self, x: f64, y: f64) {
+        unsafe { bindings::DrawTranslate(self.wand, x, y) }
+    }
+
+    /// Rotate the coordinate system clockwise by `degrees` around the origin.
+    pub fn rotate(&mut self, degrees: f64) {
+        unsafe { bindings::DrawRotate(self.wand, degrees) }
+    }
+
+    /// Scale the coordinate system by `x` horizontally and `y` vertically.
+    pub fn scale(&mut self, x: f64, y: f64) {
+        unsafe { bindings::DrawScale(self.wand, x, y) }
+    }
+
+    /// Skew the coordinate system along the x axis by `degrees`.
+    pub fn skew_x(&mut self, degrees: f64) {
+        unsafe { bindings::DrawSkewX(self.wand, degrees) }
+    }
+
+    /// Skew the coordinate system along the y axis by `degrees`.
+    pub fn skew_y(&mut self, degrees: f64) {
+        unsafe { bindings::DrawSkewY(self.wand, degrees) }
+    }
+
+    /// Set the overall canvas size recorded in the drawing's vector graphics
+    /// (the MVG `viewbox`), from `(x1, y1)` to `(x2, y2)`.
+    pub fn set_viewbox(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        // ImageMagick 7.0 takes integer coordinates here, later releases take
+        // doubles.
+        unsafe { bindings::DrawSetViewbox(self.wand, x1 as _, y1 as _, x2 as _, y2 as _) }
+    }
+
+    /// Define a pattern named `id`: everything `draw` draws becomes a tile of
+    /// the given `bounds`, `(x, y, width, height)`, that can then be used to
+    /// paint with through [`set_fill_pattern`](Self::set_fill_pattern) and
+    /// [`set_stroke_pattern`](Self::set_stroke_pattern). Coordinates inside
+    /// the pattern are relative to its tile.
+    ///
+    /// ```no_run
+    /// use magick_rust::{DrawingWand, PixelWand};
+    ///
+    /// let mut drawing = DrawingWand::new();
+    /// let mut gray = PixelWand::new();
+    /// gray.set_color("gray").unwrap();
+    /// drawing
+    ///     .define_pattern("checker", (0.0, 0.0, 20.0, 20.0), |pattern| {
+    ///         pattern.set_fill_color(&gray);
+    ///         pattern.draw_rectangle(0.0, 0.0, 9.0, 9.0);
+    ///         pattern.draw_rectangle(10.0, 10.0, 19.0, 19.0);
+    ///         Ok(())
+    ///     })
+    ///     .unwrap();
+    /// drawing.set_fill_pattern("checker").unwrap();
+    /// drawing.draw_rectangle(0.0, 0.0, 199.0, 99.0);
+    /// ```
+    pub fn define_pattern<F>(
+        &mut self,
+        id: &str,
+        bounds: (f64, f64, f64, f64),
+        draw: F,
+    ) -> Result<()>
+    where
+        F: FnOnce(&mut DrawingWand) -> Result<()>,
+    {
+        self.push_pattern(id, bounds)?;
+        let result = draw(self);
+        let popped = self.pop_pattern();
+        result.and(popped)
+    }
+
+    pub(super) fn push_pattern(&mut self, id: &str, bounds: (f64, f64, f64, f64)) -> Result<()> {
+        let c_id = CString::new(id).map_err(|_| "pattern id string contains null byte")?;
+        let (x, y, width, height) = bounds;
+        self.result_from_boolean(unsafe {
+            bindings::DrawPushPattern(self.wand, c_id.as_ptr(), x, y, width, height)
+        })
+    }
+
+    pub(super) fn pop_pattern(&mut self) -> Result<()> {
+        self.result_from_boolean(unsafe { bindings::DrawPopPattern(self.wand) })
+    }
+
+    /// Define a clip path named `id` from the shapes `draw` draws. After
+    /// [`set_clip_path`](Self::set_clip_path) with the same `id`, only the
+    /// area covered by those shapes is painted; their colors do not matter.
+    /// [`set_clip_rule`](Self::set_clip_rule) decides how overlapping shapes
+    /// combine, and [`set_clip_units`](Self::set_clip_units) whether the
+    /// coordinates are absolute or relative to the clipped shape.
+    ///
+    /// ```no_run
+    /// use magick_rust::DrawingWand;
+    ///
+    /// let mut drawing = DrawingWand::new();
+    /// drawing
+    ///     .define_clip_path("window", |clip| {
+    ///         clip.draw_circle(50.0, 50.0, 50.0, 10.0);
+    ///         Ok(())
+    ///     })
+    ///     .unwrap();
+    /// drawing.set_clip_path("window").unwrap();
+    /// drawing.draw_rectangle(0.0, 0.0, 99.0, 99.0);
+    /// ```
+    pub fn define_clip_path<F>(&mut self, id: &str, draw: F) -> Result<()>
+    where
+        F: FnOnce(&mut DrawingWand) -> Result<()>,
+    {
+        self.push_clip_path(id)?;
+        let result = draw(self);
+        self.pop_clip_path();
+        result
+    }
+
+    pub(super) fn push_clip_path(&mut self, id: &str) -> Result<()> {
+        let c_id = CString::new(id).map_err(|_| "clip path id string contains null byte")?;
+        unsafe { bindings::DrawPushClipPath(self.wand, c_id.as_ptr()) };
+        Ok(())
+    }
+
+    pub(super) fn pop_clip_path(&mut self) {
+        unsafe { bindings::DrawPopClipPath(self.wand) };
+    }
+
+    /// Define a pattern named `id` filled with a linear gradient from `start`
+    /// to `end`, spanning `bounds`, `(x, y, width, height)`; see
+    /// [`define_pattern`](Self::define_pattern). `angle` is the direction of
+    /// the gradient in degrees clockwise from north: 180 runs from top to
+    /// bottom and 90 from left to right.
+    pub fn define_linear_gradient(
+        &mut self,
+        id: &str,
+        bounds: (f64, f64, f64, f64),
+        start: &PixelWand,
+        end: &PixelWand,
+        angle: f64,
+    ) -> Result<()> {
+        self.define_gradient("gradient", id, bounds, start, end, Some(angle))
+    }
+
+    /// Define a pattern named `id` filled with a radial gradient from `inner`
+    /// at the center of `bounds`, `(x, y, width, height)`, to `outer` at its
+    /// edges; see [`define_pattern`](Self::define_pattern).
+    pub fn define_radial_gradient(
+        &mut self,
+        id: &str,
+        bounds: (f64, f64, f64, f64),
+        inner: &PixelWand,
+        outer: &PixelWand,
+    ) -> Result<()> {
+        self.define_gradient("radial-gradient", id, bounds, inner, outer, None)
+    }
+
+    /// Render the gradient with ImageMagick's `gradient:` or
+    /// `radial-gradient:` format and define a pattern that draws it.
+    fn define_gradient(
+        &mut self,
+        format: &str,
+        id: &str,
+        bounds: (f64, f64, f64, f64),
+        from: &PixelWand,
+        to: &PixelWand,
+        angle: Option<f64>,
+    ) -> Result<()> {
+        let (_, _, width, height) = bounds;
+        let mut gradient = MagickWand::new();
+        gradient.set_size(
+            width.ceil().max(1.0) as usize,
+            height.ceil().max(1.0) as usize,
+        )?;
+        if let Some(angle) = angle {
+            gradient.set_option("gradient:angle", &angle.to_string())?;
+        }
+        gradient.read_image(&format!(
+            "{format}:{}-{}",
+            from.get_color_as_string()?,
+            to.get_color_as_string()?
+        ))?;
+        self.define_pattern(id, bounds, |pattern| {
+            pattern.draw_composite(CompositeOperator::Over, 0.0, 0.0, width, height, &gradient)
+        })
+    }
+
+    /// Paint the interior of shapes drawn afterwards with the pattern named
+    /// `id` (see [`define_pattern`](Self::define_pattern)) instead of the fill
+    /// color.
+    pub fn set_fill_pattern(&mut self, id: &str) -> Result<()> {
+        let c_url = CString::new(Self::pattern_url(id))
+            .map_err(|_| "pattern id string contains null byte")?;
+        self.result_from_boolean(unsafe {
+            bindings::DrawSetFillPatternURL(self.wand, c_url.as_ptr())
+        })
+    }
+
+    /// Paint the outlines of shapes drawn afterwards with the pattern named
+    /// `id` (see [`define_pattern`](Self::define_pattern)) instead of the
+    /// stroke color.
+    pub fn set_stroke_pattern(&mut self, id: &str) -> Result<()> {
+        let c_url = CString::new(Self::pattern_url(id))
+            .map_err(|_| "pattern id string contains null byte")?;
+        self.result_from_boolean(unsafe {
+            bindings::DrawSetStrokePatternURL(self.wand, c_url.as_ptr())
+        })
+    }
+
+    /// Set the lengths of the alternating dashes and gaps used to stroke
+    /// shapes drawn afterwards, in pixels. An empty slice draws solid lines
+    /// again.
+    pub fn set_stroke_dash_array(&mut self, dash_array: &[f64]) -> Result<()> {
+        let array = if dash_array.is_empty() {
+            ptr::null()
+        } else {
+            dash_array.as_ptr()
+        };
+        self.result_from_boolean(unsafe {
+            bindings::DrawSetStrokeDashArray(self.wand, dash_array.len(), array)
+        })
+    }
+
+    /// Returns the dash and gap lengths used for stroking; empty for solid
+    /// lines.
+    pub fn get_stroke_dash_array(&self) -> Vec<f64> {
+        let mut count = 0;
+        let array = unsafe { bindings::DrawGetStrokeDashArray(self.wand, &mut count) };
+        if array.is_null() {
+            return Vec::new();
+        }
+        let dash_array = unsafe { slice::from_raw_parts(array, count) }.to_vec();
+        unsafe { bindings::RelinquishMagickMemory(array as *mut c_void) };
+        dash_array
+    }
+
+    /// Set the resolution, in dots per inch, used to render text in the
+    /// drawing.
+    pub fn set_font_resolution(&mut self, x_resolution: f64, y_resolution: f64) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::DrawSetFontResolution(self.wand, x_resolution, y_resolution)
+        })
+    }
+
+    /// Returns the horizontal and vertical text resolution in dots per inch.
+    pub fn get_font_resolution(&self) -> Result<(f64, f64)> {
+        let mut x_resolution = 0.0;
+        let mut y_resolution = 0.0;
+        self.result_from_boolean(unsafe {
+            bindings::DrawGetFontResolution(self.wand, &mut x_resolution, &mut y_resolution)
+        })
+        .map(|_| (x_resolution, y_resolution))
+    }
+
+    /// Measure `text` as it would be drawn with the current font settings,
+    /// without a `MagickWand`; see also
+    /// [`MagickWand::query_font_metrics`](crate::MagickWand::query_font_metrics).
+    /// Unless `ignore_newlines` is set, the text is broken into lines at each
+    /// newline.
+    ///
+    /// Requires ImageMagick 7.1.1-26 or later.
+    #[cfg(magick_7_1_1_26)]
+    pub fn get_type_metrics(
+        &self,
+        text: &str,
+        ignore_newlines: bool,
+    ) -> Result<crate::FontMetrics> {
+        let c_text = CString::new(text).map_err(|_| "text string contains null byte")?;
+        let mut metrics = std::mem::MaybeUninit::<bindings::TypeMetric>::zeroed();
+        self.result_from_boolean(unsafe {
+            bindings::DrawGetTypeMetrics(
+                self.wand,
+                c_text.as_ptr(),
+                ignore_newlines.into(),
+                metrics.as_mut_ptr(),
+            )
+        })?;
+        let metrics = unsafe { metrics.assume_init() };
+        Ok(crate::FontMetrics::from_type_metric(&metrics))
+    }
+
+    /// Patterns are referenced as relative URLs, i.e. `#id`.
+    fn pattern_url(id: &str) -> String {
+        format!("#{}", id.trim_start_matches('#'))
+    }
+
+    /// Draw the current image of `magick_wand` (e.g. a logo) scaled into the
+    /// rectangle at `(x, y)` of `width` by `height` pixels, blended with the
+    /// `compose` operator. A width or height of zero keeps the image's own
+    /// size. The image is embedded in the drawing, so the wand can be dropped
+    /// afterwards.
+    pub fn draw_composite(
+        &mut self,
+        compose: CompositeOperator,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        magick_wand: &MagickWand,
+    ) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::DrawComposite(
+                self.wand,
+                compose,
+                x,
+                y,
+                width,
+                height,
+                magick_wand.as_ptr(),
+            )
+        })
+    }
+
+    fn result_from_boolean(&self, result: bindings::MagickBooleanType) -> Result<()> {
+        match result {
+            bindings::MagickBooleanType::MagickTrue => Ok(()),
+            _ => Err(MagickError(self.get_exception()?.0)),
+        }
+    }
+
     fn to_point_info(coordinates: &[(f64, f64)]) -> Vec<bindings::PointInfo> {
         coordinates
             .iter()
@@ -189,6 +548,7 @@ impl DrawingWand {
         get_font_family,            set_font_family,              DrawGetFontFamily,            DrawSetFontFamily
         get_vector_graphics,        set_vector_graphics,          DrawGetVectorGraphics,        DrawSetVectorGraphics
         get_clip_path,              set_clip_path,                DrawGetClipPath,              DrawSetClipPath
+        get_density,                set_density,                  DrawGetDensity,               DrawSetDensity
     );
 
     string_set_get_unchecked!(
@@ -236,6 +596,33 @@ impl DrawingWand {
     );
 }
 
+/// A pushed graphic context of a [`DrawingWand`], returned by
+/// [`DrawingWand::push_context`]. It dereferences to the drawing wand and pops
+/// the context when dropped.
+pub struct GraphicContext<'a> {
+    drawing_wand: &'a mut DrawingWand,
+}
+
+impl Deref for GraphicContext<'_> {
+    type Target = DrawingWand;
+
+    fn deref(&self) -> &DrawingWand {
+        self.drawing_wand
+    }
+}
+
+impl DerefMut for GraphicContext<'_> {
+    fn deref_mut(&mut self) -> &mut DrawingWand {
+        self.drawing_wand
+    }
+}
+
+impl Drop for GraphicContext<'_> {
+    fn drop(&mut self) {
+        unsafe { bindings::PopDrawingWand(self.drawing_wand.wand) };
+    }
+}
+
 impl fmt::Debug for DrawingWand {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f, "DrawingWand {{")?;
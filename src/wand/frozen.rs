@@ -0,0 +1,179 @@
+/*
+ * Copyright 2026 Nathan Fiedler
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::fmt;
+
+use crate::result::{MagickError, Result};
+use crate::{
+    ColorspaceType, CompressionType, ImageType, InterlaceType, MagickWand, OrientationType,
+    ResolutionType,
+};
+
+/// A read-only `MagickWand` that may be shared between threads.
+///
+/// `MagickWand` itself is `Send` but not `Sync`: even its `&self` methods move
+/// the wand's internal image iterator or touch the pixel cache, neither of
+/// which is safe to do from two threads at once. Freezing a wand (see
+/// [`MagickWand::freeze`]) pins its iterator to the first image and then only
+/// exposes operations that read plain image attributes, or that work on a
+/// private clone of the wand, which makes the frozen wand `Sync`.
+///
+/// The intended use is decoding a master image once and sharing it, typically
+/// behind an `Arc`, between request handlers that each [`thaw`](Self::thaw) a
+/// copy to resize or encode:
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use std::thread;
+/// use magick_rust::{FilterType, MagickWand};
+///
+/// fn main() -> Result<(), magick_rust::MagickError> {
+///     let mut wand = MagickWand::new();
+///     wand.read_image("master.png")?;
+///     let master = Arc::new(wand.freeze()?);
+///
+///     let handles: Vec<_> = [64, 128, 256]
+///         .into_iter()
+///         .map(|size| {
+///             let master = Arc::clone(&master);
+///             thread::spawn(move || {
+///                 let mut thumb = master.thaw();
+///                 thumb.resize_image(size, size, FilterType::Lanczos)?;
+///                 thumb.write_image_blob("png")
+///             })
+///         })
+///         .collect();
+///     for handle in handles {
+///         handle.join().unwrap()?;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct FrozenWand {
+    wand: MagickWand,
+}
+
+// SAFETY: `FrozenWand` never hands out the inner wand by reference and never
+// calls a MagickWand function that moves the iterator, writes image data or
+// settings, or reads pixels through the (per-thread) pixel cache nexus. The
+// attribute getters only read fields of the current image, and cloning goes
+// through ImageMagick's own locking (image and pixel cache reference counts,
+// splay tree semaphores), so concurrent calls through `&FrozenWand` are sound.
+// `freeze` rejects a wand without images, so the getters never fail and thus
+// never write to or read back the wand's shared exception.
+unsafe impl Sync for FrozenWand {}
+
+impl MagickWand {
+    /// Freeze this wand into a [`FrozenWand`] that can be shared between
+    /// threads. The iterator is moved to the first image, so the frozen wand's
+    /// getters describe that image.
+    ///
+    /// Fails if the wand holds no images.
+    pub fn freeze(mut self) -> Result<FrozenWand> {
+        if self.get_number_images() == 0 {
+            return Err(MagickError(
+                "cannot freeze a wand without images".to_string(),
+            ));
+        }
+        self.set_first_iterator();
+        Ok(FrozenWand { wand: self })
+    }
+}
+
+/// Generate read-only forwarding accessors on [`FrozenWand`]. Each delegates to
+/// the eponymous [`MagickWand`] getter, which only reads image attributes.
+macro_rules! frozen_getters {
+    ($($name:ident() -> $ret:ty;)*) => {
+        impl FrozenWand {
+            $(
+                #[doc = concat!(
+                    "Read the first image's value; see [`MagickWand::",
+                    stringify!($name), "`]."
+                )]
+                pub fn $name(&self) -> $ret {
+                    self.wand.$name()
+                }
+            )*
+        }
+    };
+}
+
+frozen_getters! {
+    get_number_images() -> usize;
+    get_image_width() -> usize;
+    get_image_height() -> usize;
+    get_image_page() -> (usize, usize, isize, isize);
+    get_image_resolution() -> Result<(f64, f64)>;
+    get_image_format() -> Result<String>;
+    get_image_filename() -> Result<String>;
+    get_image_colorspace() -> ColorspaceType;
+    get_image_compression() -> CompressionType;
+    get_image_compression_quality() -> usize;
+    get_image_delay() -> usize;
+    get_image_depth() -> usize;
+    get_image_interlace_scheme() -> InterlaceType;
+    get_image_iterations() -> usize;
+    get_image_orientation() -> OrientationType;
+    get_image_type() -> ImageType;
+    get_image_units() -> ResolutionType;
+    get_image_alpha_channel() -> bool;
+}
+
+impl FrozenWand {
+    /// Return a new, independent `MagickWand` holding a copy of the frozen
+    /// images, which may be freely modified. Pixel data is shared
+    /// copy-on-write, so this is cheap until the copy is changed.
+    pub fn thaw(&self) -> MagickWand {
+        self.wand.clone()
+    }
+
+    /// Unwrap the frozen wand, returning the original `MagickWand`.
+    pub fn into_inner(self) -> MagickWand {
+        self.wand
+    }
+
+    /// Encode the first image in the given format; see
+    /// [`MagickWand::write_image_blob`]. The encoding is done on a private
+    /// copy, so the frozen wand is left untouched.
+    pub fn write_image_blob(&self, format: &str) -> Result<Vec<u8>> {
+        self.thaw().write_image_blob(format)
+    }
+
+    /// Encode all images in the given format; see
+    /// [`MagickWand::write_images_blob`]. The encoding is done on a private
+    /// copy, so the frozen wand is left untouched.
+    pub fn write_images_blob(&self, format: &str) -> Result<Vec<u8>> {
+        self.thaw().write_images_blob(format)
+    }
+}
+
+impl TryFrom<MagickWand> for FrozenWand {
+    type Error = MagickError;
+
+    fn try_from(wand: MagickWand) -> Result<Self> {
+        wand.freeze()
+    }
+}
+
+impl fmt::Debug for FrozenWand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "FrozenWand {{")?;
+        writeln!(f, "    NumberImages: {}", self.get_number_images())?;
+        writeln!(f, "    Width: {}", self.get_image_width())?;
+        writeln!(f, "    Height: {}", self.get_image_height())?;
+        writeln!(f, "    Format: {:?}", self.get_image_format())?;
+        writeln!(f, "}}")
+    }
+}
@@ -118,8 +118,10 @@ macro_rules! wand_common {
         // The wand types should be safe to drop in a different thread
         unsafe impl Send for $wand {}
 
-        // Probably shouldn't implement Sync because some methods might not be
-        // safe to call on the same wand from different threads.
+        // Deliberately not Sync: methods that change the wand take `&mut self`,
+        // but even the `&self` getters move the image iterator or go through
+        // the pixel cache, which must not happen from two threads at once. Use
+        // `FrozenWand` to share a read-only `MagickWand` between threads.
         // unsafe impl Sync for $wand {}
     };
 }
@@ -140,10 +142,14 @@ macro_rules! set_get {
         $(
             #[doc = concat!("Wraps ImageMagick's `", stringify!($c_get), "` function.")]
             pub fn $get(&self) -> $typ {
+                #[cfg(feature = "tracing")]
+                let _span = tracing::trace_span!(stringify!($c_get)).entered();
                 unsafe { crate::bindings::$c_get(self.wand).into() }
             }
             #[doc = concat!("Wraps ImageMagick's `", stringify!($c_set), "` function.")]
             pub fn $set(&mut self, v: $typ) -> Result<()> {
+                #[cfg(feature = "tracing")]
+                let _span = tracing::trace_span!(stringify!($c_set)).entered();
                 match unsafe { crate::bindings::$c_set(self.wand, v.into()) } {
                     crate::bindings::MagickBooleanType::MagickTrue => Ok(()),
                     _ => Err(MagickError(concat!(stringify!($set), " returned false").to_string()))
@@ -267,8 +273,10 @@ macro_rules! color_set_get {
             pub fn $get(&self) -> f64 {
                 unsafe { crate::bindings::$c_get(self.wand) }
             }
-            #[doc = concat!("Wraps ImageMagick's `", stringify!($c_get_quantum), "` function.")]
-            pub fn $get_quantum(&self) -> bindings::Quantum {
+            #[doc = concat!("Wraps ImageMagick's `", stringify!($c_get_quantum), "` function. The")]
+            /// value is relative to [`QUANTUM_RANGE`](crate::QUANTUM_RANGE); see
+            /// [`Quantum`](crate::Quantum) for how HDRI builds differ.
+            pub fn $get_quantum(&self) -> crate::Quantum {
                 unsafe { crate::bindings::$c_get_quantum(self.wand) }
             }
             #[doc = concat!("Wraps ImageMagick's `", stringify!($c_set), "` function.")]
@@ -276,7 +284,7 @@ macro_rules! color_set_get {
                 unsafe { crate::bindings::$c_set(self.wand, v) }
             }
             #[doc = concat!("Wraps ImageMagick's `", stringify!($c_set_quantum), "` function.")]
-            pub fn $set_quantum(&mut self, v: bindings::Quantum) {
+            pub fn $set_quantum(&mut self, v: crate::Quantum) {
                 unsafe { crate::bindings::$c_set_quantum(self.wand, v) }
             }
         )*
@@ -298,7 +306,9 @@ macro_rules! mutations {
     ($($(#[$attr:meta])* $c_fun:ident => $fun:ident($($arg:ident: $ty:ty),*))*) => {
         $(
             $(#[$attr])*
-            pub fn $fun(&self $(, $arg: $ty)*) -> Result<()> {
+            pub fn $fun(&mut self $(, $arg: $ty)*) -> Result<()> {
+                #[cfg(feature = "tracing")]
+                let _span = tracing::trace_span!(stringify!($c_fun)).entered();
                 match unsafe { bindings::$c_fun(self.wand $(, $arg.into())*) } {
                     bindings::MagickBooleanType::MagickTrue => Ok(()),
                     _ => Err(MagickError(concat!(stringify!($c_fun), " invocation failed").to_string()))
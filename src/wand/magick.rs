@@ -14,6 +14,7 @@
  * limitations under the License.
  */
 use std::ffi::{CStr, CString};
+use std::path::Path;
 use std::{fmt, ptr, slice};
 
 #[cfg(target_os = "freebsd")]
@@ -29,15 +30,18 @@ use super::{MagickFalse, MagickTrue};
 use crate::result::Result;
 
 use super::{DrawingWand, PixelWand};
+#[cfg(magick_7_0_8_0)]
+use crate::AutoThresholdMethod;
 #[cfg(any(target_os = "linux", target_os = "macos"))]
 use crate::ResourceType;
 use crate::bindings::MagickBooleanType;
 use crate::{
-    AlphaChannelOption, AutoThresholdMethod, ChannelType, ColorspaceType, CompositeOperator,
-    CompressionType, DisposeType, DitherMethod, EndianType, FilterType, GravityType, Image,
-    ImageType, Images, ImagesMut, InterlaceType, KernelInfo, LayerMethod, MagickEvaluateOperator,
-    MagickFunction, MetricType, MorphologyMethod, OrientationType, PixelInterpolateMethod,
-    PixelMask, RenderingIntent, ResolutionType, StatisticType, VirtualPixelMethod,
+    AlphaChannelOption, CaptionOptions, ChannelType, Color, ColorspaceType, ComplexOperator,
+    Component, CompositeOperator, CompressionType, DisposeType, DitherMethod, EndianType,
+    FilterType, FontMetrics, GravityType, HoughLine, Image, ImageType, Images, ImagesMut,
+    InterlaceType, KernelBuilder, KernelInfo, LayerMethod, MagickEvaluateOperator, MagickFunction,
+    MetricType, MorphologyMethod, NoiseType, OrientationType, PixelInterpolateMethod, PixelMask,
+    QUANTUM_RANGE, RenderingIntent, ResolutionType, StatisticType, VirtualPixelMethod,
 };
 
 wand_common!(
@@ -71,8 +75,50 @@ impl MagickWand {
         )
     }
 
+    /// Creates a wand with a one pixel high image holding each color of
+    /// `colors` once, for use as the palette of
+    /// [`remap_image`](Self::remap_image).
+    pub fn new_from_palette(colors: &[Color]) -> Result<MagickWand> {
+        if colors.is_empty() {
+            return Err(MagickError("palette has no colors".to_string()));
+        }
+        let opaque = colors.iter().all(|color| color.alpha >= 1.0);
+        let background = if opaque {
+            colors[0].to_pixel_wand()
+        } else {
+            Color {
+                alpha: 0.0,
+                ..colors[0]
+            }
+            .to_pixel_wand()
+        };
+        let mut wand = MagickWand::new();
+        wand.new_image(colors.len(), 1, &background)?;
+        let (map, pixels): (&str, Vec<f64>) = if opaque {
+            let pixels = colors
+                .iter()
+                .flat_map(|color| [color.red, color.green, color.blue])
+                .collect();
+            ("RGB", pixels)
+        } else {
+            let pixels = colors
+                .iter()
+                .flat_map(|color| [color.red, color.green, color.blue, color.alpha])
+                .collect();
+            ("RGBA", pixels)
+        };
+        wand.import_image_pixels_double(0, 0, colors.len(), 1, &pixels, map)?;
+        Ok(wand)
+    }
+
+    /// Creates a palette wand, as with [`new_from_palette`](Self::new_from_palette),
+    /// from a GIMP (`.gpl`) or Adobe Color Table (`.act`) palette file.
+    pub fn new_from_palette_file<P: AsRef<Path>>(path: P) -> Result<MagickWand> {
+        Self::new_from_palette(&Color::read_palette(path)?)
+    }
+
     /// Add a blank image canvas of the given dimensions and background color.
-    pub fn new_image(&self, columns: usize, rows: usize, background: &PixelWand) -> Result<()> {
+    pub fn new_image(&mut self, columns: usize, rows: usize, background: &PixelWand) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickNewImage(self.wand, columns, rows, background.as_ptr())
         })
@@ -150,8 +196,151 @@ impl MagickWand {
         )
     }
 
+    /// Measure `text` as it would be drawn with the font, point size, and other
+    /// text settings of `drawing_wand`, without drawing it. Newlines are not
+    /// treated specially; see
+    /// [`query_multiline_font_metrics`](Self::query_multiline_font_metrics).
+    ///
+    /// ImageMagick measures text against an image (its resolution affects the
+    /// result); if the wand holds no image, a 1x1 scratch image is used.
+    pub fn query_font_metrics(
+        &self,
+        drawing_wand: &DrawingWand,
+        text: &str,
+    ) -> Result<FontMetrics> {
+        self.font_metrics(drawing_wand, text, false)
+    }
+
+    /// Like [`query_font_metrics`](Self::query_font_metrics), but breaks the
+    /// text into lines at each newline, so that `text_width` is the width of
+    /// the longest line and `text_height` the height of all lines.
+    pub fn query_multiline_font_metrics(
+        &self,
+        drawing_wand: &DrawingWand,
+        text: &str,
+    ) -> Result<FontMetrics> {
+        self.font_metrics(drawing_wand, text, true)
+    }
+
+    fn font_metrics(
+        &self,
+        drawing_wand: &DrawingWand,
+        text: &str,
+        multiline: bool,
+    ) -> Result<FontMetrics> {
+        if self.get_number_images() == 0 {
+            let mut scratch = MagickWand::new();
+            scratch.new_image(1, 1, &PixelWand::new())?;
+            return scratch.font_metrics(drawing_wand, text, multiline);
+        }
+        let c_text = CString::new(text).map_err(|_| "text string contains null byte")?;
+        let query = if multiline {
+            bindings::MagickQueryMultilineFontMetrics
+        } else {
+            bindings::MagickQueryFontMetrics
+        };
+        let values = unsafe { query(self.wand, drawing_wand.as_ptr(), c_text.as_ptr()) };
+        self.result_from_ptr(values, |values| {
+            let metrics = FontMetrics::from_slice(unsafe { slice::from_raw_parts(values, 13) });
+            unsafe { bindings::MagickRelinquishMemory(values as *mut c_void) };
+            metrics
+        })
+    }
+
+    /// Find the largest point size, no larger than the current font size of
+    /// `drawing_wand`, at which `text` (which may contain newlines) fits within
+    /// `width` by `height` pixels. The size is set on `drawing_wand` and
+    /// returned.
+    ///
+    /// The search is accurate to half a point and stops at one point; if the
+    /// text does not fit even then, the font size is left unchanged and an
+    /// error is returned.
+    pub fn fit_font_size(
+        &self,
+        drawing_wand: &mut DrawingWand,
+        text: &str,
+        width: f64,
+        height: f64,
+    ) -> Result<f64> {
+        let fits = |drawing_wand: &mut DrawingWand, size: f64| -> Result<bool> {
+            drawing_wand.set_font_size(size);
+            let metrics = self.query_multiline_font_metrics(drawing_wand, text)?;
+            Ok(metrics.text_width <= width && metrics.text_height <= height)
+        };
+        let original = drawing_wand.get_font_size();
+        if fits(drawing_wand, original)? {
+            return Ok(original);
+        }
+        let mut low = 1.0;
+        if original <= low || !fits(drawing_wand, low)? {
+            drawing_wand.set_font_size(original);
+            return Err(MagickError(format!(
+                "text does not fit in {width}x{height} at any font size"
+            )));
+        }
+        let mut high = original;
+        while high - low > 0.5 {
+            let middle = (low + high) / 2.0;
+            if fits(drawing_wand, middle)? {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        drawing_wand.set_font_size(low);
+        Ok(low)
+    }
+
+    /// Render `text` as a new image with ImageMagick's `caption:` format,
+    /// which wraps the text at `options.width` pixels; see [`CaptionOptions`].
+    ///
+    /// The text is used literally: `%` escapes, backslash escapes, reading the
+    /// text from a file with a leading `@`, and frame selection with a
+    /// trailing `[...]` are all disabled.
+    pub fn render_caption(text: &str, options: &CaptionOptions) -> Result<MagickWand> {
+        Self::render_text("caption:", text, options)
+    }
+
+    /// Render `text` as a new image with ImageMagick's `label:` format, which
+    /// draws a single line without wrapping; see [`CaptionOptions`]. The text
+    /// is used literally, as with [`render_caption`](Self::render_caption).
+    pub fn render_label(text: &str, options: &CaptionOptions) -> Result<MagickWand> {
+        Self::render_text("label:", text, options)
+    }
+
+    fn render_text(format: &str, text: &str, options: &CaptionOptions) -> Result<MagickWand> {
+        let mut wand = MagickWand::new();
+        wand.set_size(options.width.unwrap_or(0), options.height.unwrap_or(0))?;
+        if let Some(font) = &options.font {
+            wand.set_font(font)?;
+        }
+        if let Some(pointsize) = options.pointsize {
+            wand.set_pointsize(pointsize)?;
+        }
+        if let Some(gravity) = options.gravity {
+            wand.set_gravity(gravity)?;
+        }
+        if let Some(fill) = &options.fill {
+            wand.set_option("fill", &fill.get_color_as_string()?)?;
+        }
+        if let Some(background) = &options.background {
+            wand.set_background_color(background)?;
+        }
+        if let Some(spacing) = options.interline_spacing {
+            wand.set_option("interline-spacing", &spacing.to_string())?;
+        }
+        // The filename is interpreted (`%` escapes, a leading `@`, a trailing
+        // `[...]` frame selection), so it only refers to the text, which is
+        // inserted verbatim from the option.
+        wand.set_option(TEXT_OPTION, text)?;
+        let result = wand.read_image(&format!("{format}%[option:{TEXT_OPTION}]"));
+        let c_key = CString::new(TEXT_OPTION).expect("option key contains null byte");
+        unsafe { bindings::MagickDeleteOption(wand.wand, c_key.as_ptr()) };
+        result.map(|()| wand)
+    }
+
     /// Set the image label property to the given string.
-    pub fn label_image(&self, label: &str) -> Result<()> {
+    pub fn label_image(&mut self, label: &str) -> Result<()> {
         let c_label = CString::new(label).map_err(|_| "label string contains null byte")?;
         self.result_from_boolean(unsafe { bindings::MagickLabelImage(self.wand, c_label.as_ptr()) })
     }
@@ -159,7 +348,7 @@ impl MagickWand {
     /// Write all images in the wand to the named file. When `adjoin` is `true`
     /// and the format supports it, all images are written into a single file;
     /// otherwise each image is written to a separately numbered file.
-    pub fn write_images(&self, path: &str, adjoin: bool) -> Result<()> {
+    pub fn write_images(&mut self, path: &str, adjoin: bool) -> Result<()> {
         let c_name = CString::new(path).map_err(|_| "path string contains null byte")?;
         self.result_from_boolean(unsafe {
             bindings::MagickWriteImages(self.wand, c_name.as_ptr(), adjoin.into())
@@ -167,13 +356,13 @@ impl MagickWand {
     }
 
     /// Read the image data from the named file.
-    pub fn read_image(&self, path: &str) -> Result<()> {
+    pub fn read_image(&mut self, path: &str) -> Result<()> {
         let c_name = CString::new(path).map_err(|_| "path string contains null byte")?;
         self.result_from_boolean(unsafe { bindings::MagickReadImage(self.wand, c_name.as_ptr()) })
     }
 
     /// Read the image data from the vector of bytes.
-    pub fn read_image_blob<T: AsRef<[u8]>>(&self, data: T) -> Result<()> {
+    pub fn read_image_blob<T: AsRef<[u8]>>(&mut self, data: T) -> Result<()> {
         let int_slice = data.as_ref();
         let size = int_slice.len();
         self.result_from_boolean(unsafe {
@@ -183,14 +372,14 @@ impl MagickWand {
 
     /// Same as read_image, but reads only the width, height, size and format of an image,
     /// without reading data.
-    pub fn ping_image(&self, path: &str) -> Result<()> {
+    pub fn ping_image(&mut self, path: &str) -> Result<()> {
         let c_name = CString::new(path).map_err(|_| "path string contains null byte")?;
         self.result_from_boolean(unsafe { bindings::MagickPingImage(self.wand, c_name.as_ptr()) })
     }
 
     /// Same as read_image, but reads only the width, height, size and format of an image,
     /// without reading data.
-    pub fn ping_image_blob<T: AsRef<[u8]>>(&self, data: T) -> Result<()> {
+    pub fn ping_image_blob<T: AsRef<[u8]>>(&mut self, data: T) -> Result<()> {
         let int_slice = data.as_ref();
         let size = int_slice.len();
         self.result_from_boolean(unsafe {
@@ -247,7 +436,7 @@ impl MagickWand {
 
     /// Compose another image onto self at (x, y) using composition_operator
     pub fn compose_images(
-        &self,
+        &mut self,
         reference: &MagickWand,
         composition_operator: CompositeOperator,
         clip_to_self: bool,
@@ -268,7 +457,7 @@ impl MagickWand {
 
     /// Compose another image onto self with gravity using composition_operator
     pub fn compose_images_gravity(
-        &self,
+        &mut self,
         reference: &MagickWand,
         composition_operator: CompositeOperator,
         gravity_type: GravityType,
@@ -295,14 +484,18 @@ impl MagickWand {
     }
 
     /// Replaces colors in the image from a color lookup table.
-    pub fn clut_image(&self, clut_wand: &MagickWand, method: PixelInterpolateMethod) -> Result<()> {
+    pub fn clut_image(
+        &mut self,
+        clut_wand: &MagickWand,
+        method: PixelInterpolateMethod,
+    ) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickClutImage(self.wand, clut_wand.wand, method)
         })
     }
 
     /// Replaces colors in the image using a Hald color lookup table (a Hald CLUT image).
-    pub fn hald_clut_image(&self, clut_wand: &MagickWand) -> Result<()> {
+    pub fn hald_clut_image(&mut self, clut_wand: &MagickWand) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickHaldClutImage(self.wand, clut_wand.wand)
         })
@@ -323,43 +516,20 @@ impl MagickWand {
 
     /// Sets the size of the wand, used to read images larger than the canvas or
     /// to size formats (e.g. PostScript) that have no inherent dimensions.
-    pub fn set_size(&self, columns: usize, rows: usize) -> Result<()> {
+    pub fn set_size(&mut self, columns: usize, rows: usize) -> Result<()> {
         self.result_from_boolean(unsafe { bindings::MagickSetSize(self.wand, columns, rows) })
     }
 
-    /// Define two 'quantum_range' functions because the bindings::QuantumRange symbol
-    /// is not available if hdri is disabled in the compiled ImageMagick libs
-    #[cfg(not(feature = "disable-hdri"))]
-    fn quantum_range(&self) -> Result<f64> {
-        Ok(bindings::QuantumRange)
-    }
-
-    /// with disable-hdri enabled we define our own quantum_range
-    /// values lifted directly from magick-type.h
-    #[cfg(feature = "disable-hdri")]
-    fn quantum_range(&self) -> Result<f64> {
-        match bindings::MAGICKCORE_QUANTUM_DEPTH {
-            8 => Ok(255.0f64),
-            16 => Ok(65535.0f64),
-            32 => Ok(4294967295.0f64),
-            64 => Ok(18446744073709551615.0f64),
-            _ => Err(MagickError(
-                "Quantum depth must be one of 8, 16, 32 or 64".to_string(),
-            )),
-        }
-    }
-
-    /// Level an image. Black and white points are multiplied with QuantumRange to
+    /// Level an image. Black and white points are given as fractions of the
+    /// quantum range (`0.0..=1.0`) and multiplied with [`QUANTUM_RANGE`] to
     /// decrease dependencies on the end user.
-    pub fn level_image(&self, black_point: f64, gamma: f64, white_point: f64) -> Result<()> {
-        let quantum_range = self.quantum_range()?;
-
+    pub fn level_image(&mut self, black_point: f64, gamma: f64, white_point: f64) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickLevelImage(
                 self.wand,
-                black_point * quantum_range,
+                black_point * QUANTUM_RANGE,
                 gamma,
-                white_point * quantum_range,
+                white_point * QUANTUM_RANGE,
             )
         })
     }
@@ -367,29 +537,108 @@ impl MagickWand {
     /// Applies the reversed [level_image](Self::level_image). It compresses the full range of color values, so
     /// that they lie between the given black and white points. Gamma is applied before the values
     /// are mapped. It can be used to de-contrast a greyscale image to the exact levels specified.
-    pub fn levelize_image(&self, black_point: f64, gamma: f64, white_point: f64) -> Result<()> {
-        let quantum_range = self.quantum_range()?;
-
+    pub fn levelize_image(&mut self, black_point: f64, gamma: f64, white_point: f64) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickLevelizeImage(
                 self.wand,
-                black_point * quantum_range,
+                black_point * QUANTUM_RANGE,
                 gamma,
-                white_point * quantum_range,
+                white_point * QUANTUM_RANGE,
             )
         })
     }
 
     /// MagickNormalizeImage enhances the contrast of a color image by adjusting the pixels color
     /// to span the entire range of colors available
-    pub fn normalize_image(&self) -> Result<()> {
+    pub fn normalize_image(&mut self) -> Result<()> {
         self.result_from_boolean(unsafe { bindings::MagickNormalizeImage(self.wand) })
     }
 
+    /// Enhances contrast by equalizing the histogram of the image.
+    pub fn equalize_image(&mut self) -> Result<()> {
+        self.result_from_boolean(unsafe { bindings::MagickEqualizeImage(self.wand) })
+    }
+
+    /// Contrast limited adaptive histogram equalization (CLAHE): the image is
+    /// equalized in tiles of `width` x `height` pixels with histograms of
+    /// `bins` bins, and `clip_limit` caps the contrast gain (3 is a common
+    /// value) so that noise is not amplified in flat areas.
+    ///
+    /// Requires ImageMagick 7.0.9 or later.
+    #[cfg(magick_7_0_9_0)]
+    pub fn clahe_image(
+        &mut self,
+        width: usize,
+        height: usize,
+        bins: usize,
+        clip_limit: f64,
+    ) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickCLAHEImage(self.wand, width, height, bins as f64, clip_limit)
+        })
+    }
+
+    /// Enhances local contrast, like an unsharp mask with a large `radius`.
+    /// `strength` is a percentage; 100 doubles the local contrast.
+    ///
+    /// Requires ImageMagick 7.0.8 or later.
+    #[cfg(magick_7_0_8_0)]
+    pub fn local_contrast_image(&mut self, radius: f64, strength: f64) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickLocalContrastImage(self.wand, radius, strength)
+        })
+    }
+
+    /// Gamma-corrects the image; values above 1 brighten it and values below
+    /// 1 darken it.
+    pub fn gamma_image(&mut self, gamma: f64) -> Result<()> {
+        self.result_from_boolean(unsafe { bindings::MagickGammaImage(self.wand, gamma) })
+    }
+
+    /// Stretches the intensities linearly so that `black_point` pixels become
+    /// black and `white_point` pixels become white; both are pixel counts.
+    pub fn linear_stretch_image(&mut self, black_point: f64, white_point: f64) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickLinearStretchImage(self.wand, black_point, white_point)
+        })
+    }
+
+    /// Increases (`sharpen` is true) or decreases the contrast of the image,
+    /// making light areas lighter and dark areas darker or the reverse.
+    pub fn contrast_image(&mut self, sharpen: bool) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickContrastImage(self.wand, sharpen.into())
+        })
+    }
+
+    /// Corrects the white balance with the gray world assumption in the Lab
+    /// colorspace.
+    ///
+    /// Requires ImageMagick 7.1 or later.
+    #[cfg(magick_7_1_0_0)]
+    pub fn white_balance_image(&mut self) -> Result<()> {
+        self.result_from_boolean(unsafe { bindings::MagickWhiteBalanceImage(self.wand) })
+    }
+
+    /// Applies an ASC CDL (American Society of Cinematographers Color
+    /// Decision List) color correction, given as XML with a
+    /// `ColorCorrection` element holding `SOPNode` (slope, offset, power)
+    /// and `SATNode` (saturation) values.
+    pub fn color_decision_list_image(&mut self, color_correction_collection: &str) -> Result<()> {
+        let c_ccc = CString::new(color_correction_collection)
+            .map_err(|_| "color correction collection string contains null byte")?;
+        self.result_from_boolean(unsafe {
+            bindings::MagickColorDecisionListImage(self.wand, c_ccc.as_ptr())
+        })
+    }
+
     /// MagickOrderedDitherImage performs an ordered dither based on a number of pre-defined
     /// dithering threshold maps, but over multiple intensity levels, which can be different for
     /// different channels, according to the input arguments.
-    pub fn ordered_dither_image(&self, threshold_map: &str) -> Result<()> {
+    ///
+    /// See [`magick_threshold_maps`](crate::magick_threshold_maps) for the
+    /// available maps.
+    pub fn ordered_dither_image(&mut self, threshold_map: &str) -> Result<()> {
         let c_threshold_map =
             CString::new(threshold_map).map_err(|_| "threshold_map string contains null byte")?;
 
@@ -398,6 +647,99 @@ impl MagickWand {
         })
     }
 
+    /// Turns every pixel brighter than `threshold`, given as a fraction of the
+    /// quantum range, white and every other pixel black.
+    pub fn threshold_image(&mut self, threshold: f64) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickThresholdImage(self.wand, threshold * QUANTUM_RANGE)
+        })
+    }
+
+    /// Like [`threshold_image`](Self::threshold_image), but only for the given
+    /// channels.
+    pub fn threshold_image_channel(&mut self, channel: ChannelType, threshold: f64) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickThresholdImageChannel(self.wand, channel, threshold * QUANTUM_RANGE)
+        })
+    }
+
+    /// Thresholds each pixel against the mean of its `width` x `height`
+    /// neighborhood plus `bias` (a fraction of the quantum range, may be
+    /// negative), which copes with uneven lighting when binarizing documents.
+    pub fn adaptive_threshold_image(
+        &mut self,
+        width: usize,
+        height: usize,
+        bias: f64,
+    ) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickAdaptiveThresholdImage(self.wand, width, height, bias * QUANTUM_RANGE)
+        })
+    }
+
+    /// Turns every channel value below the corresponding channel of
+    /// `threshold` black, leaving the others unchanged.
+    pub fn black_threshold_image(&mut self, threshold: &PixelWand) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickBlackThresholdImage(self.wand, threshold.as_ptr())
+        })
+    }
+
+    /// Turns every channel value above the corresponding channel of
+    /// `threshold` white, leaving the others unchanged.
+    pub fn white_threshold_image(&mut self, threshold: &PixelWand) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickWhiteThresholdImage(self.wand, threshold.as_ptr())
+        })
+    }
+
+    /// Thresholds each pixel against a random value between `low` and `high`,
+    /// given as fractions of the quantum range.
+    pub fn random_threshold_image(&mut self, low: f64, high: f64) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickRandomThresholdImage(
+                self.wand,
+                low * QUANTUM_RANGE,
+                high * QUANTUM_RANGE,
+            )
+        })
+    }
+
+    /// Turns pixels between `low_white` and `high_white` white and pixels
+    /// outside `low_black` and `high_black` black, with soft ramps in
+    /// between; all four are fractions of the quantum range.
+    ///
+    /// Requires ImageMagick 7.0.10-37 or later.
+    #[cfg(magick_7_0_10_37)]
+    pub fn range_threshold_image(
+        &mut self,
+        low_black: f64,
+        low_white: f64,
+        high_white: f64,
+        high_black: f64,
+    ) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickRangeThresholdImage(
+                self.wand,
+                low_black * QUANTUM_RANGE,
+                low_white * QUANTUM_RANGE,
+                high_white * QUANTUM_RANGE,
+                high_black * QUANTUM_RANGE,
+            )
+        })
+    }
+
+    /// Turns pixels whose color lies between `start` and `stop`, channel by
+    /// channel, white and all other pixels black.
+    ///
+    /// Requires ImageMagick 7.0.10-37 or later.
+    #[cfg(magick_7_0_10_37)]
+    pub fn color_threshold_image(&mut self, start: &PixelWand, stop: &PixelWand) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickColorThresholdImage(self.wand, start.as_ptr(), stop.as_ptr())
+        })
+    }
+
     /// Apply sigmoidal contrast to the image
     ///
     /// Adjusts the contrast of an image with a non-linear sigmoidal contrast algorithm. Increase
@@ -411,26 +753,24 @@ impl MagickWand {
     /// * `strength`: strength of the contrast, the larger the number the more 'threshold-like' it becomes.
     /// * `midpoint`: midpoint of the function as a number in range [0, 1]
     pub fn sigmoidal_contrast_image(
-        &self,
+        &mut self,
         sharpen: bool,
         strength: f64,
         midpoint: f64,
     ) -> Result<()> {
-        let quantum_range = self.quantum_range()?;
-
         self.result_from_boolean(unsafe {
             bindings::MagickSigmoidalContrastImage(
                 self.wand,
                 sharpen.into(),
                 strength,
-                midpoint * quantum_range,
+                midpoint * QUANTUM_RANGE,
             )
         })
     }
 
     /// Extend the image as defined by the geometry, gravity, and wand background color. Set the
     /// (x,y) offset of the geometry to move the original wand relative to the extended wand.
-    pub fn extend_image(&self, width: usize, height: usize, x: isize, y: isize) -> Result<()> {
+    pub fn extend_image(&mut self, width: usize, height: usize, x: isize, y: isize) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickExtentImage(self.wand, width, height, x, y)
         })
@@ -440,7 +780,7 @@ impl MagickWand {
     /// Passing `None` (or an empty profile) for `profile` removes the named
     /// profile.
     pub fn profile_image<'a, T: Into<Option<&'a [u8]>>>(
-        &self,
+        &mut self,
         name: &str,
         profile: T,
     ) -> Result<()> {
@@ -461,22 +801,22 @@ impl MagickWand {
     }
 
     /// Strip the image of all profiles and comments.
-    pub fn strip_image(&self) -> Result<()> {
+    pub fn strip_image(&mut self) -> Result<()> {
         self.result_from_boolean(unsafe { bindings::MagickStripImage(self.wand) })
     }
 
     /// Flip the image vertically (mirror about the horizontal axis).
-    pub fn flip_image(&self) -> Result<()> {
+    pub fn flip_image(&mut self) -> Result<()> {
         self.result_from_boolean(unsafe { bindings::MagickFlipImage(self.wand) })
     }
 
     /// Negate the colors in the image, producing its photographic negative.
-    pub fn negate_image(&self) -> Result<()> {
+    pub fn negate_image(&mut self) -> Result<()> {
         self.result_from_boolean(unsafe { bindings::MagickNegateImage(self.wand, MagickTrue) })
     }
 
     /// Flop the image horizontally (mirror about the vertical axis).
-    pub fn flop_image(&self) -> Result<()> {
+    pub fn flop_image(&mut self) -> Result<()> {
         self.result_from_boolean(unsafe { bindings::MagickFlopImage(self.wand) })
     }
 
@@ -484,26 +824,96 @@ impl MagickWand {
     /// `radius` and standard deviation (`sigma`), both in pixels. For reasonable
     /// results the radius should be larger than sigma; use a radius of 0 to let
     /// ImageMagick select a suitable radius.
-    pub fn blur_image(&self, radius: f64, sigma: f64) -> Result<()> {
+    pub fn blur_image(&mut self, radius: f64, sigma: f64) -> Result<()> {
         self.result_from_boolean(unsafe { bindings::MagickBlurImage(self.wand, radius, sigma) })
     }
 
     /// Blur the image with a Gaussian operator of the given `radius` and
     /// standard deviation (`sigma`), both in pixels. Use a radius of 0 to let
     /// ImageMagick select a suitable radius.
-    pub fn gaussian_blur_image(&self, radius: f64, sigma: f64) -> Result<()> {
+    pub fn gaussian_blur_image(&mut self, radius: f64, sigma: f64) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickGaussianBlurImage(self.wand, radius, sigma)
         })
     }
 
+    /// Simulate motion blur: the image is blurred with a one-sided Gaussian
+    /// of the given `radius` and `sigma` in the direction of `angle` degrees,
+    /// as if the objects moved that way.
+    pub fn motion_blur_image(&mut self, radius: f64, sigma: f64, angle: f64) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickMotionBlurImage(self.wand, radius, sigma, angle)
+        })
+    }
+
+    /// Blur the image around its center, as if it was spinning by `angle`
+    /// degrees.
+    pub fn rotational_blur_image(&mut self, angle: f64) -> Result<()> {
+        self.result_from_boolean(unsafe { bindings::MagickRotationalBlurImage(self.wand, angle) })
+    }
+
+    /// Blur only the pixels whose contrast with their neighbors is below
+    /// `threshold`, given as a fraction of the quantum range, so that noise
+    /// and smooth areas are blurred while edges stay sharp.
+    pub fn selective_blur_image(&mut self, radius: f64, sigma: f64, threshold: f64) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickSelectiveBlurImage(self.wand, radius, sigma, threshold * QUANTUM_RANGE)
+        })
+    }
+
+    /// Blur the image less near edges and more in smooth areas, with a
+    /// Gaussian of at most the given `radius` and `sigma`. Use a radius of 0
+    /// to let ImageMagick select a suitable radius.
+    pub fn adaptive_blur_image(&mut self, radius: f64, sigma: f64) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickAdaptiveBlurImage(self.wand, radius, sigma)
+        })
+    }
+
+    /// Sharpen the image more near edges and less in smooth areas, with a
+    /// Gaussian of at most the given `radius` and `sigma`. Use a radius of 0
+    /// to let ImageMagick select a suitable radius.
+    pub fn adaptive_sharpen_image(&mut self, radius: f64, sigma: f64) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickAdaptiveSharpenImage(self.wand, radius, sigma)
+        })
+    }
+
+    /// Sharpen the image with an unsharp mask: the difference between the
+    /// image and a Gaussian blur of the given `radius` and `sigma` is added
+    /// back, multiplied by `gain`, wherever it exceeds `threshold` (a fraction
+    /// of the quantum range). This is the usual way to restore crispness after
+    /// downscaling, e.g. `unsharp_mask_image(0.0, 0.75, 0.75, 0.008)`.
+    pub fn unsharp_mask_image(
+        &mut self,
+        radius: f64,
+        sigma: f64,
+        gain: f64,
+        threshold: f64,
+    ) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickUnsharpMaskImage(self.wand, radius, sigma, gain, threshold)
+        })
+    }
+
+    /// Apply the Kuwahara edge-preserving smoothing filter: each pixel takes
+    /// the mean of the least varying of the four quadrants around it, within
+    /// `radius`. `sigma` is the standard deviation of the Gaussian used to
+    /// compute the means.
+    ///
+    /// Requires ImageMagick 7.0.8 or later.
+    #[cfg(magick_7_0_8_0)]
+    pub fn kuwahara_image(&mut self, radius: f64, sigma: f64) -> Result<()> {
+        self.result_from_boolean(unsafe { bindings::MagickKuwaharaImage(self.wand, radius, sigma) })
+    }
+
     /// Replace each pixel with corresponding statistic from the neighborhood of the specified width and height.
     ///
     /// * `statistic_type`: the statistic type (e.g. `StatisticType::Median`, `StatisticType::Mode`, etc.).
     /// * `width`: the width of the pixel neighborhood.
     /// * `height`: the height of the pixel neighborhood.
     pub fn statistic_image(
-        &self,
+        &mut self,
         statistic_type: StatisticType,
         width: usize,
         height: usize,
@@ -513,15 +923,104 @@ impl MagickWand {
         })
     }
 
+    /// Add noise of the given type to the image. `attenuate` scales the
+    /// amount of noise; 1.0 is the default strength.
+    pub fn add_noise_image(&mut self, noise_type: NoiseType, attenuate: f64) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickAddNoiseImage(self.wand, noise_type, attenuate)
+        })
+    }
+
+    /// Reduce speckle noise while preserving edges.
+    pub fn despeckle_image(&mut self) -> Result<()> {
+        self.result_from_boolean(unsafe { bindings::MagickDespeckleImage(self.wand) })
+    }
+
+    /// Reduce noise with a digital filter that smooths pixels while
+    /// preserving edges.
+    pub fn enhance_image(&mut self) -> Result<()> {
+        self.result_from_boolean(unsafe { bindings::MagickEnhanceImage(self.wand) })
+    }
+
+    /// Remove noise with a wavelet transform: detail below `threshold`, given
+    /// as a fraction of the quantum range, is removed. `softness` (0 to 1)
+    /// softens the cut-off. See
+    /// [`estimate_noise`](Self::estimate_noise) for picking a threshold.
+    ///
+    /// Requires ImageMagick 7.0.8 or later.
+    #[cfg(magick_7_0_8_0)]
+    pub fn wavelet_denoise_image(&mut self, threshold: f64, softness: f64) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickWaveletDenoiseImage(self.wand, threshold * QUANTUM_RANGE, softness)
+        })
+    }
+
+    /// Remove noise with [`wavelet_denoise_image`](Self::wavelet_denoise_image),
+    /// with a threshold of three times the noise level found by
+    /// [`estimate_noise`](Self::estimate_noise). This works well for scanned
+    /// documents, whose noise varies from one scanner to the next. Returns the
+    /// threshold used.
+    ///
+    /// Requires ImageMagick 7.0.8 or later.
+    #[cfg(magick_7_0_8_0)]
+    pub fn auto_wavelet_denoise_image(&mut self, softness: f64) -> Result<f64> {
+        let threshold = 3.0 * self.estimate_noise()?;
+        self.wavelet_denoise_image(threshold, softness)?;
+        Ok(threshold)
+    }
+
+    /// Segment the image with mean shift: each pixel moves to the mean of the
+    /// pixels within a `width` x `height` window whose color is within
+    /// `color_distance` (a fraction of the quantum range), which smooths
+    /// regions while keeping their boundaries.
+    ///
+    /// Requires ImageMagick 7.0.8 or later.
+    #[cfg(magick_7_0_8_0)]
+    pub fn mean_shift_image(
+        &mut self,
+        width: usize,
+        height: usize,
+        color_distance: f64,
+    ) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickMeanShiftImage(self.wand, width, height, color_distance * QUANTUM_RANGE)
+        })
+    }
+
+    /// Estimate the standard deviation of the noise in the image, as a
+    /// fraction of the quantum range, with Immerkær's method: the grayscale
+    /// image is convolved with a kernel that cancels out smooth areas and
+    /// edges, and the noise level follows from the mean absolute response.
+    pub fn estimate_noise(&self) -> Result<f64> {
+        const KERNEL: [f64; 9] = [1.0, -2.0, 1.0, -2.0, 4.0, -2.0, 1.0, -2.0, 1.0];
+        let mut gray = self.clone();
+        gray.set_image_alpha_channel(AlphaChannelOption::Off)?;
+        gray.transform_image_colorspace(ColorspaceType::GRAY)?;
+        // The positive and negative halves of the response are measured
+        // separately, since without HDRI the negative values are clamped.
+        let mut mean_absolute = 0.0;
+        for sign in [1.0, -1.0] {
+            let kernel = KernelBuilder::default()
+                .set_size((3, 3))
+                .set_values(&KERNEL.map(|value| sign * value))
+                .build()?;
+            let mut response = gray.clone();
+            response.convolve_image(&kernel)?;
+            response.evaluate_image(MagickEvaluateOperator::Max, 0.0)?;
+            mean_absolute += response.get_image_mean()?.0;
+        }
+        Ok(std::f64::consts::FRAC_PI_2.sqrt() * mean_absolute / 6.0 / QUANTUM_RANGE)
+    }
+
     /// Calculate median for each pixel's neighborhood.
     ///
     /// See [statistic_image](Self::statistic_image)
-    pub fn median_blur_image(&self, width: usize, height: usize) -> Result<()> {
+    pub fn median_blur_image(&mut self, width: usize, height: usize) -> Result<()> {
         self.statistic_image(StatisticType::Median, width, height)
     }
 
     /// Adaptively resize the currently selected image.
-    pub fn adaptive_resize_image(&self, width: usize, height: usize) -> Result<()> {
+    pub fn adaptive_resize_image(&mut self, width: usize, height: usize) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickAdaptiveResizeImage(self.wand, width, height)
         })
@@ -529,7 +1028,7 @@ impl MagickWand {
 
     /// Rotate the currently selected image by the given number of degrees,
     /// filling any empty space with the background color of a given PixelWand
-    pub fn rotate_image(&self, background: &PixelWand, degrees: f64) -> Result<()> {
+    pub fn rotate_image(&mut self, background: &PixelWand, degrees: f64) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickRotateImage(self.wand, background.as_ptr(), degrees)
         })
@@ -538,11 +1037,11 @@ impl MagickWand {
     /// Trim the image removing the background color from the edges.
     ///
     /// `fuzz` is the color-matching tolerance in raw quantum units
-    /// (`0..=QuantumRange`), *not* a fraction or percentage. To express the
-    /// ImageMagick command line's `-fuzz 15%`, multiply: e.g. on a Q16 build use
-    /// `0.15 * 65535.0`. Passing a small value such as `0.15` is effectively a
-    /// zero-tolerance trim and will not remove a noisy border.
-    pub fn trim_image(&self, fuzz: f64) -> Result<()> {
+    /// (`0..=QUANTUM_RANGE`), *not* a fraction or percentage. To express the
+    /// ImageMagick command line's `-fuzz 15%`, multiply: `0.15 * QUANTUM_RANGE`.
+    /// Passing a small value such as `0.15` is effectively a zero-tolerance
+    /// trim and will not remove a noisy border.
+    pub fn trim_image(&mut self, fuzz: f64) -> Result<()> {
         self.result_from_boolean(unsafe { bindings::MagickTrimImage(self.wand, fuzz) })
     }
 
@@ -585,7 +1084,7 @@ impl MagickWand {
     }
 
     /// Reset the Wand page canvas and position.
-    pub fn reset_image_page(&self, page_geometry: &str) -> Result<()> {
+    pub fn reset_image_page(&mut self, page_geometry: &str) -> Result<()> {
         let c_page_geometry =
             CString::new(page_geometry).map_err(|_| "page_geometry contains null byte")?;
         self.result_from_boolean(unsafe {
@@ -706,7 +1205,7 @@ impl MagickWand {
     }
 
     /// Set the named image property.
-    pub fn set_image_property(&self, name: &str, value: &str) -> Result<()> {
+    pub fn set_image_property(&mut self, name: &str, value: &str) -> Result<()> {
         let c_name = CString::new(name).map_err(|_| "name string contains null byte")?;
         let c_value = CString::new(value).map_err(|_| "value string contains null byte")?;
         self.result_from_boolean(unsafe {
@@ -725,7 +1224,7 @@ impl MagickWand {
     /// Sets the image sampling factors.
     ///
     /// samplingFactors: An array of floats representing the sampling factor for each color component (in RGB order).
-    pub fn set_sampling_factors(&self, samplingFactors: &[f64]) -> Result<()> {
+    pub fn set_sampling_factors(&mut self, samplingFactors: &[f64]) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickSetSamplingFactors(
                 self.wand,
@@ -751,6 +1250,49 @@ impl MagickWand {
         }
     }
 
+    /// Returns up to `number_colors` dominant colors of the current image, each
+    /// with its share of the pixels, most common first. The colors are found
+    /// by clustering a downsampled copy (k-means on ImageMagick 7.0.10-37 or
+    /// later, color quantization before that), so the image is unchanged.
+    ///
+    /// Use [`Color::sort_by_distinctness`] to order them for a varied palette
+    /// instead.
+    pub fn dominant_colors(&self, number_colors: usize) -> Result<Vec<(Color, f64)>> {
+        if number_colors == 0 {
+            return Err(MagickError("number of colors must be positive".to_string()));
+        }
+        let mut wand = MagickWand::new_from_image(&self.get_image()?)?;
+        // Clustering cost grows with the pixel count, and a small sample gives
+        // the same dominant colors.
+        if wand.get_image_width() > 128 || wand.get_image_height() > 128 {
+            wand.fit(128, 128);
+        }
+        #[cfg(magick_7_0_10_37)]
+        wand.kmeans(number_colors, 100, 0.01)?;
+        #[cfg(not(magick_7_0_10_37))]
+        wand.quantize_image(
+            number_colors,
+            ColorspaceType::sRGB,
+            0,
+            DitherMethod::No,
+            false,
+        )?;
+        let histogram = wand
+            .get_image_histogram()
+            .ok_or_else(|| MagickError("failed to compute the image histogram".to_string()))?;
+        let total: usize = histogram.iter().map(PixelWand::get_color_count).sum();
+        let mut colors: Vec<(Color, f64)> = histogram
+            .iter()
+            .map(|pixel| {
+                let share = pixel.get_color_count() as f64 / total.max(1) as f64;
+                (Color::from_pixel_wand(pixel), share)
+            })
+            .collect();
+        colors.sort_by(|a, b| b.1.total_cmp(&a.1));
+        colors.truncate(number_colors);
+        Ok(colors)
+    }
+
     /// Sharpens an image. We convolve the image with a Gaussian operator of the
     /// given radius and standard deviation (sigma). For reasonable results, the
     /// radius should be larger than sigma. Use a radius of 0 and SharpenImage()
@@ -760,19 +1302,19 @@ impl MagickWand {
     ///
     /// sigma: the standard deviation of the Gaussian, in pixels.
     ///
-    pub fn sharpen_image(&self, radius: f64, sigma: f64) -> Result<()> {
+    pub fn sharpen_image(&mut self, radius: f64, sigma: f64) -> Result<()> {
         self.result_from_boolean(unsafe { bindings::MagickSharpenImage(self.wand, radius, sigma) })
     }
 
     /// Set the background color.
-    pub fn set_background_color(&self, pixel_wand: &PixelWand) -> Result<()> {
+    pub fn set_background_color(&mut self, pixel_wand: &PixelWand) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickSetBackgroundColor(self.wand, pixel_wand.as_ptr())
         })
     }
 
     /// Set the image background color.
-    pub fn set_image_background_color(&self, pixel_wand: &PixelWand) -> Result<()> {
+    pub fn set_image_background_color(&mut self, pixel_wand: &PixelWand) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickSetImageBackgroundColor(self.wand, pixel_wand.as_ptr())
         })
@@ -789,7 +1331,8 @@ impl MagickWand {
     }
 
     /// Returns the range of the image as a pair `(minima, maxima)`, in raw
-    /// quantum values (i.e. `0..=QuantumRange`). The range is computed over the
+    /// quantum values (i.e. `0..=QUANTUM_RANGE`, though with HDRI the values
+    /// may be negative or exceed [`QUANTUM_RANGE`]). The range is computed over the
     /// channels currently enabled by the image's channel mask; by default that
     /// is every channel. To restrict the range to a single channel, see
     /// [`MagickWand::get_image_channel_range`].
@@ -802,8 +1345,21 @@ impl MagickWand {
         .map(|_| (minima, maxima))
     }
 
+    /// Returns the mean and standard deviation of the image as a pair
+    /// `(mean, standard_deviation)`, in raw quantum values, over the channels
+    /// enabled by the image's channel mask, as with
+    /// [`MagickWand::get_image_range`].
+    pub fn get_image_mean(&self) -> Result<(f64, f64)> {
+        let mut mean = 0f64;
+        let mut standard_deviation = 0f64;
+        self.result_from_boolean(unsafe {
+            bindings::MagickGetImageMean(self.wand, &mut mean, &mut standard_deviation)
+        })
+        .map(|_| (mean, standard_deviation))
+    }
+
     /// Returns the range of a single channel as a pair `(minima, maxima)`, in
-    /// raw quantum values (i.e. `0..=QuantumRange`). This is the equivalent of
+    /// raw quantum values, as with [`MagickWand::get_image_range`]. This is the equivalent of
     /// PHP Imagick's `getImageChannelRange`, which was removed from the C API in
     /// ImageMagick 7: it is implemented by temporarily setting the image channel
     /// mask, reading the range, then restoring the previous mask.
@@ -815,14 +1371,14 @@ impl MagickWand {
     }
 
     /// Sets the image resolution
-    pub fn set_image_resolution(&self, x_resolution: f64, y_resolution: f64) -> Result<()> {
+    pub fn set_image_resolution(&mut self, x_resolution: f64, y_resolution: f64) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickSetImageResolution(self.wand, x_resolution, y_resolution)
         })
     }
 
     /// Sets the wand resolution
-    pub fn set_resolution(&self, x_resolution: f64, y_resolution: f64) -> Result<()> {
+    pub fn set_resolution(&mut self, x_resolution: f64, y_resolution: f64) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickSetResolution(self.wand, x_resolution, y_resolution)
         })
@@ -832,9 +1388,19 @@ impl MagickWand {
     /// a photo darkroom by sepia toning. The `threshold` controls the extent of
     /// the tone darkening and is given as a fraction of the quantum range
     /// (a value around 0.8, i.e. 80%, is typical).
-    pub fn sepia_tone_image(&self, threshold: f64) -> Result<()> {
+    pub fn sepia_tone_image(&mut self, threshold: f64) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickSepiaToneImage(self.wand, threshold * QUANTUM_RANGE)
+        })
+    }
+
+    /// Applies a special effect to the image, similar to the effect achieved
+    /// in a photo darkroom by selectively exposing areas of photo sensitive
+    /// paper to light. Pixels above `threshold`, given as a fraction of the
+    /// quantum range, are negated.
+    pub fn solarize_image(&mut self, threshold: f64) -> Result<()> {
         self.result_from_boolean(unsafe {
-            bindings::MagickSepiaToneImage(self.wand, threshold * self.quantum_range()?)
+            bindings::MagickSolarizeImage(self.wand, threshold * QUANTUM_RANGE)
         })
     }
 
@@ -908,7 +1474,7 @@ impl MagickWand {
 
     /// Resize the image to the specified width and height, using the
     /// specified filter type.
-    pub fn resize_image(&self, width: usize, height: usize, filter: FilterType) -> Result<()> {
+    pub fn resize_image(&mut self, width: usize, height: usize, filter: FilterType) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickResizeImage(self.wand, width, height, filter)
         })
@@ -919,7 +1485,7 @@ impl MagickWand {
     /// Effectively resizes image to (current width * `width_scale`, current height *
     /// `height_scale`)
     pub fn scale_image(
-        &self,
+        &mut self,
         width_scale: f64,
         height_scale: f64,
         filter: FilterType,
@@ -943,7 +1509,7 @@ impl MagickWand {
     /// Resize the image to the specified width and height, using the
     /// 'thumbnail' optimizations which remove a lot of image meta-data with the goal
     /// of producing small low cost images suited for display on the web.
-    pub fn thumbnail_image(&self, width: usize, height: usize) -> Result<()> {
+    pub fn thumbnail_image(&mut self, width: usize, height: usize) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickThumbnailImage(self.wand, width, height)
         })
@@ -951,7 +1517,7 @@ impl MagickWand {
 
     /// Extract a region of the image. The width and height is used as the size
     /// of the region. X and Y is the offset.
-    pub fn crop_image(&self, width: usize, height: usize, x: isize, y: isize) -> Result<()> {
+    pub fn crop_image(&mut self, width: usize, height: usize, x: isize, y: isize) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickCropImage(self.wand, width, height, x, y)
         })
@@ -961,14 +1527,14 @@ impl MagickWand {
     ///
     /// This is incredibly fast, as it does 1-1 pixel mapping for downscales, and box filtering for
     /// upscales
-    pub fn sample_image(&self, width: usize, height: usize) -> Result<()> {
+    pub fn sample_image(&mut self, width: usize, height: usize) -> Result<()> {
         self.result_from_boolean(unsafe { bindings::MagickSampleImage(self.wand, width, height) })
     }
 
     /// Resample the image to the specified horizontal and vertical resolution, using the
     /// specified filter type.
     pub fn resample_image(
-        &self,
+        &mut self,
         x_resolution: f64,
         y_resolution: f64,
         filter: FilterType,
@@ -980,7 +1546,7 @@ impl MagickWand {
 
     /// Rescale the image using seam carving algorithm
     pub fn liquid_rescale_image(
-        &self,
+        &mut self,
         width: usize,
         height: usize,
         delta_x: f64,
@@ -992,13 +1558,36 @@ impl MagickWand {
     }
 
     /// Implodes the image towards the center by the specified percentage
-    pub fn implode(&self, amount: f64, method: PixelInterpolateMethod) -> Result<()> {
+    pub fn implode(&mut self, amount: f64, method: PixelInterpolateMethod) -> Result<()> {
         self.result_from_boolean(unsafe { bindings::MagickImplodeImage(self.wand, amount, method) })
     }
 
+    /// Simulates a Polaroid picture: the image gets a white border with
+    /// `caption` below it, is rotated by `angle` degrees, and casts a shadow
+    /// on a transparent background. The caption is rendered with the font,
+    /// size and colors of `drawing_wand`.
+    pub fn polaroid_image(
+        &mut self,
+        drawing_wand: &DrawingWand,
+        caption: &str,
+        angle: f64,
+        method: PixelInterpolateMethod,
+    ) -> Result<()> {
+        let c_caption = CString::new(caption).map_err(|_| "caption string contains null byte")?;
+        self.result_from_boolean(unsafe {
+            bindings::MagickPolaroidImage(
+                self.wand,
+                drawing_wand.as_ptr(),
+                c_caption.as_ptr(),
+                angle,
+                method,
+            )
+        })
+    }
+
     /// Resize the image to fit within the given dimensions, maintaining
     /// the current aspect ratio.
-    pub fn fit(&self, width: usize, height: usize) {
+    pub fn fit(&mut self, width: usize, height: usize) {
         let mut width_ratio = width as f64;
         width_ratio /= self.get_image_width() as f64;
         let mut height_ratio = height as f64;
@@ -1032,12 +1621,12 @@ impl MagickWand {
     /// suitable for viewing (i.e. top-left orientation).
     ///
     /// Returns `true` if successful or `false` if an error occurred.
-    pub fn auto_orient(&self) -> bool {
+    pub fn auto_orient(&mut self) -> bool {
         unsafe { bindings::MagickAutoOrientImage(self.wand) == MagickTrue }
     }
 
     /// Write the current image to the provided path.
-    pub fn write_image(&self, path: &str) -> Result<()> {
+    pub fn write_image(&mut self, path: &str) -> Result<()> {
         let c_name = CString::new(path).map_err(|_| "name string contains null byte")?;
         self.result_from_boolean(unsafe { bindings::MagickWriteImage(self.wand, c_name.as_ptr()) })
     }
@@ -1046,7 +1635,7 @@ impl MagickWand {
     ///
     /// The `format` argument may be any ImageMagick supported image
     /// format (e.g. GIF, JPEG, PNG, etc).
-    pub fn write_image_blob(&self, format: &str) -> Result<Vec<u8>> {
+    pub fn write_image_blob(&mut self, format: &str) -> Result<Vec<u8>> {
         let c_format = CString::new(format).map_err(|_| "format string contains null byte")?;
         let mut length: size_t = 0;
         let blob = unsafe {
@@ -1062,7 +1651,7 @@ impl MagickWand {
     ///
     /// The `format` argument may be any ImageMagick supported image
     /// format (e.g. GIF, JPEG, PNG, etc).
-    pub fn write_images_blob(&self, format: &str) -> Result<Vec<u8>> {
+    pub fn write_images_blob(&mut self, format: &str) -> Result<Vec<u8>> {
         let c_format = CString::new(format).map_err(|_| "format string contains null byte")?;
         let mut length: size_t = 0;
         let blob = unsafe {
@@ -1106,6 +1695,57 @@ impl MagickWand {
         })
     }
 
+    /// Removes the image mask of type `pixel_mask`.
+    pub fn remove_image_mask(&mut self, pixel_mask: PixelMask) -> Result<()> {
+        self.set_image_mask(pixel_mask, &MagickWand::new())
+    }
+
+    /// Sets the image mask of type `pixel_mask` to the area covered by what
+    /// `shape` draws, whatever its colors. With `PixelMask::Write`, later
+    /// operations only change the pixels inside the shape; with
+    /// `PixelMask::Read`, they only read the pixels inside it.
+    pub fn set_image_mask_from_drawing(
+        &mut self,
+        pixel_mask: PixelMask,
+        shape: &DrawingWand,
+    ) -> Result<()> {
+        let mut transparent = PixelWand::new();
+        transparent.set_color("none")?;
+        let mut mask = MagickWand::new();
+        mask.new_image(
+            self.get_image_width(),
+            self.get_image_height(),
+            &transparent,
+        )?;
+        mask.draw_image(shape)?;
+        mask.set_image_alpha_channel(AlphaChannelOption::Extract)?;
+        self.set_image_mask(pixel_mask, &mask)
+    }
+
+    /// Runs `operation` with the area covered by `shape` as the write mask
+    /// (see [`set_image_mask_from_drawing`](Self::set_image_mask_from_drawing)),
+    /// so that only the pixels inside the shape change, then removes the mask.
+    ///
+    /// ```no_run
+    /// use magick_rust::{DrawingWand, MagickWand};
+    ///
+    /// let mut wand = MagickWand::new();
+    /// wand.read_image("photo.jpg").unwrap();
+    /// let mut face = DrawingWand::new();
+    /// face.draw_ellipse(120.0, 80.0, 40.0, 50.0, 0.0, 360.0);
+    /// wand.with_drawing_mask(&face, |wand| wand.blur_image(0.0, 8.0))
+    ///     .unwrap();
+    /// ```
+    pub fn with_drawing_mask<F, T>(&mut self, shape: &DrawingWand, operation: F) -> Result<T>
+    where
+        F: FnOnce(&mut MagickWand) -> Result<T>,
+    {
+        self.set_image_mask_from_drawing(PixelMask::Write, shape)?;
+        let result = operation(self);
+        let removed = self.remove_image_mask(PixelMask::Write);
+        result.and_then(|value| removed.map(|()| value))
+    }
+
     /// Set image channel mask
     pub fn set_image_channel_mask(&mut self, option: ChannelType) -> ChannelType {
         unsafe { bindings::MagickSetImageChannelMask(self.wand, option) }
@@ -1119,10 +1759,21 @@ impl MagickWand {
         self.result_from_boolean(unsafe { bindings::MagickEvaluateImage(self.wand, op, val) })
     }
 
+    /// Combines all images in the wand pixel by pixel with `op`, returning a
+    /// new wand with a single image. `Mean` averages the frames (stacking
+    /// exposures to reduce noise), `Median` takes the middle value of each
+    /// pixel across the frames (removing objects that move between frames),
+    /// and `Min`, `Max`, `Sum`, `RootMeanSquare` and the arithmetic operators
+    /// work likewise.
+    pub fn evaluate_images(&self, op: MagickEvaluateOperator) -> Result<MagickWand> {
+        let wand_ptr = unsafe { bindings::MagickEvaluateImages(self.wand, op) };
+        self.result_from_ptr(wand_ptr, MagickWand::from_ptr)
+    }
+
     /// Surround the image with a border of the color defined
     /// by the `pixel_wand`.
     pub fn border_image(
-        &self,
+        &mut self,
         pixel_wand: &PixelWand,
         width: usize,
         height: usize,
@@ -1145,8 +1796,8 @@ impl MagickWand {
     /// * `fill`: the color painted into the matched region (e.g. `"none"` for
     ///   transparency).
     /// * `fuzz`: how far a pixel's color may differ from the target and still be
-    ///   considered a match, in raw quantum units (`0..=QuantumRange`). For a
-    ///   percentage, multiply: e.g. 75% on a Q16 build is `0.75 * 65535.0`.
+    ///   considered a match, in raw quantum units (`0..=QUANTUM_RANGE`). For a
+    ///   percentage, multiply: e.g. 75% is `0.75 * QUANTUM_RANGE`.
     /// * `border_color`: the target color to flood. With `invert == false`, the
     ///   region of connected pixels matching `border_color` (within `fuzz`),
     ///   starting from `(x, y)`, is painted with `fill`. With `invert == true`,
@@ -1155,7 +1806,7 @@ impl MagickWand {
     /// * `x`, `y`: the seed pixel where the fill begins.
     /// * `invert`: invert the sense of the match, as described above.
     pub fn floodfill_paint_image(
-        &self,
+        &mut self,
         fill: &PixelWand,
         fuzz: f64,
         border_color: &PixelWand,
@@ -1189,12 +1840,12 @@ impl MagickWand {
     /// * `alpha`: the transparency to apply to matched pixels, where `1.0` is
     ///   fully opaque and `0.0` is fully transparent.
     /// * `fuzz`: how far a pixel's color may differ from `target` and still be
-    ///   considered a match, in raw quantum units (`0..=QuantumRange`). For a
-    ///   percentage, multiply: e.g. 10% on a Q16 build is `0.10 * 65535.0`.
+    ///   considered a match, in raw quantum units (`0..=QUANTUM_RANGE`). For a
+    ///   percentage, multiply: e.g. 10% is `0.10 * QUANTUM_RANGE`.
     /// * `invert`: when `true`, paint the pixels that do *not* match `target`
     ///   instead.
     pub fn transparent_paint_image(
-        &self,
+        &mut self,
         target: &PixelWand,
         alpha: f64,
         fuzz: f64,
@@ -1212,7 +1863,7 @@ impl MagickWand {
     }
 
     /// Simulate an image shadow
-    pub fn shadow_image(&self, alpha: f64, sigma: f64, x: isize, y: isize) -> Result<()> {
+    pub fn shadow_image(&mut self, alpha: f64, sigma: f64, x: isize, y: isize) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickShadowImage(self.wand, alpha, sigma, x, y)
         })
@@ -1297,7 +1948,7 @@ impl MagickWand {
     /// list with `next_image`; it leaves the wand positioned *before* the first
     /// image rather than *on* it (see [`Self::set_first_iterator`] for that).
     /// See <https://imagemagick.org/api/magick-wand.php#MagickResetIterator> for more information.
-    pub fn reset_iterator(&self) {
+    pub fn reset_iterator(&mut self) {
         unsafe {
             bindings::MagickResetIterator(self.wand);
         }
@@ -1305,7 +1956,7 @@ impl MagickWand {
 
     /// Set the wand iterator to the first image.
     /// See <https://imagemagick.org/api/magick-wand.php#MagickSetFirstIterator> for more information.
-    pub fn set_first_iterator(&self) {
+    pub fn set_first_iterator(&mut self) {
         unsafe {
             bindings::MagickSetFirstIterator(self.wand);
         }
@@ -1313,7 +1964,7 @@ impl MagickWand {
 
     /// Set the wand iterator to the last image.
     /// See <https://imagemagick.org/api/magick-wand.php#MagickSetLastIterator> for more information.
-    pub fn set_last_iterator(&self) {
+    pub fn set_last_iterator(&mut self) {
         unsafe {
             bindings::MagickSetLastIterator(self.wand);
         }
@@ -1324,7 +1975,7 @@ impl MagickWand {
     /// Returns `true` while the iterator advanced onto a valid image, and
     /// `false` once it has moved past the last image.
     /// See <https://imagemagick.org/api/magick-image.php#MagickNextImage> for more information.
-    pub fn next_image(&self) -> bool {
+    pub fn next_image(&mut self) -> bool {
         let res = unsafe { bindings::MagickNextImage(self.wand) };
         res == MagickTrue
     }
@@ -1334,7 +1985,7 @@ impl MagickWand {
     /// Returns `true` while the iterator stepped back onto a valid image, and
     /// `false` once it has moved before the first image.
     /// See <https://imagemagick.org/api/magick-image.php#MagickPreviousImage> for more information.
-    pub fn previous_image(&self) -> bool {
+    pub fn previous_image(&mut self) -> bool {
         let res = unsafe { bindings::MagickPreviousImage(self.wand) };
         res == MagickTrue
     }
@@ -1365,13 +2016,16 @@ impl MagickWand {
     /// down to a binary black & white image. Included algorithms are
     /// Kapur, Otsu, and Triangle methods.
     /// See <https://imagemagick.org/api/magick-image.php#MagickAutoThresholdImage> for more information.
-    pub fn auto_threshold(&self, method: AutoThresholdMethod) -> Result<()> {
+    ///
+    /// Requires ImageMagick 7.0.8 or later.
+    #[cfg(magick_7_0_8_0)]
+    pub fn auto_threshold(&mut self, method: AutoThresholdMethod) -> Result<()> {
         self.result_from_boolean(unsafe { bindings::MagickAutoThresholdImage(self.wand, method) })
     }
 
     /// Set the image colorspace, transforming (unlike `set_image_colorspace`) image data in
     /// the process.
-    pub fn transform_image_colorspace(&self, colorspace: ColorspaceType) -> Result<()> {
+    pub fn transform_image_colorspace(&mut self, colorspace: ColorspaceType) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickTransformImageColorspace(self.wand, colorspace)
         })
@@ -1379,7 +2033,7 @@ impl MagickWand {
 
     /// Reduce the number of colors in the image.
     pub fn quantize_image(
-        &self,
+        &mut self,
         number_of_colors: usize,
         colorspace: ColorspaceType,
         tree_depth: usize,
@@ -1400,7 +2054,7 @@ impl MagickWand {
 
     /// Reduce the number of colors in the images.
     pub fn quantize_images(
-        &self,
+        &mut self,
         number_of_colors: usize,
         colorspace: ColorspaceType,
         tree_depth: usize,
@@ -1419,6 +2073,67 @@ impl MagickWand {
         })
     }
 
+    /// Replaces the colors of the image with the closest colors of the images
+    /// in `palette`, such as a wand made by
+    /// [`new_from_palette`](Self::new_from_palette).
+    ///
+    /// * `dither_method`: how the error is spread to neighboring pixels;
+    ///   `DitherMethod::No` gives flat areas of color.
+    pub fn remap_image(&mut self, palette: &MagickWand, dither_method: DitherMethod) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickRemapImage(self.wand, palette.wand, dither_method)
+        })
+    }
+
+    /// Changes every pixel that matches `target` to `fill`.
+    ///
+    /// * `fuzz`: how far a pixel's color may differ from `target` and still be
+    ///   considered a match, in raw quantum units, as for
+    ///   [`transparent_paint_image`](Self::transparent_paint_image).
+    /// * `invert`: when `true`, paint the pixels that do *not* match `target`
+    ///   instead.
+    pub fn opaque_paint_image(
+        &mut self,
+        target: &PixelWand,
+        fill: &PixelWand,
+        fuzz: f64,
+        invert: bool,
+    ) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickOpaquePaintImage(
+                self.wand,
+                target.as_ptr(),
+                fill.as_ptr(),
+                fuzz,
+                invert.into(),
+            )
+        })
+    }
+
+    /// Reduces each channel to `levels` evenly spaced values.
+    pub fn posterize_image(&mut self, levels: usize, dither_method: DitherMethod) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickPosterizeImage(self.wand, levels, dither_method)
+        })
+    }
+
+    /// Blends `color` into the image. The red, green and blue of `blend` are
+    /// the fraction of `color` mixed into each channel, e.g. `rgb(30%,30%,30%)`.
+    pub fn colorize_image(&mut self, color: &PixelWand, blend: &PixelWand) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickColorizeImage(self.wand, color.as_ptr(), blend.as_ptr())
+        })
+    }
+
+    /// Tints the midtones of the image with `color`, leaving black and white
+    /// unchanged. The red, green and blue of `blend` weigh the tint of each
+    /// channel, e.g. `rgb(50%,50%,50%)`.
+    pub fn tint_image(&mut self, color: &PixelWand, blend: &PixelWand) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickTintImage(self.wand, color.as_ptr(), blend.as_ptr())
+        })
+    }
+
     /// Applies an arithmetic, relational, or logical expression to an image. Use these operators
     /// to lighten or darken an image, to increase or decrease contrast in an image, or to produce
     /// the "negative" of an image.
@@ -1444,20 +2159,24 @@ impl MagickWand {
     ///     Ok(())
     /// }
     /// ```
-    pub fn function_image(&self, function: MagickFunction, args: &[f64]) -> Result<()> {
+    pub fn function_image(&mut self, function: MagickFunction, args: &[f64]) -> Result<()> {
         let num_of_args: size_t = args.len();
         self.result_from_boolean(unsafe {
             bindings::MagickFunctionImage(self.wand, function, num_of_args, args.as_ptr())
         })
     }
 
-    /// Returns an image where each pixel is the sum of the pixels in the image sequence after
-    /// applying its corresponding terms (coefficient and degree pairs).
+    /// Blends the image sequence into a single image, where each pixel is the
+    /// sum over the frames of `weight * value^exponent`, with pixel values
+    /// normalized to 0.0 to 1.0. For example `[0.7, 1.0, 0.3, 1.0]` mixes 70%
+    /// of the first frame with 30% of the second, and `[1.0, 2.0]` squares a
+    /// single frame.
     ///
-    /// * `terms`: the list of polynomial coefficients and degree pairs and a constant.
-    pub fn polynomial_image(&self, terms: &[f64]) -> Result<()> {
-        if terms.len() & 1 != 1 {
-            return Err(MagickError("no constant coefficient given".to_string()));
+    /// * `terms`: a `weight, exponent` pair for each frame; frames without a
+    ///   pair are left out. A trailing odd value is ignored.
+    pub fn polynomial_image(&mut self, terms: &[f64]) -> Result<()> {
+        if terms.len() < 2 {
+            return Err(MagickError("no polynomial terms given".to_string()));
         }
 
         let num_of_terms: size_t = terms.len() >> 1;
@@ -1470,7 +2189,7 @@ impl MagickWand {
     /// Applies a custom convolution kernel to the image.
     ///
     /// * `kernel_info`: An array of doubles representing the convolution kernel.
-    pub fn convolve_image(&self, kernel_info: &KernelInfo) -> Result<()> {
+    pub fn convolve_image(&mut self, kernel_info: &KernelInfo) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickConvolveImage(self.wand, kernel_info.get_ptr())
         })
@@ -1482,7 +2201,7 @@ impl MagickWand {
     /// * `iterations`: apply the operation this many times (or no change). A value of -1 means loop until no change found. How this is applied may depend on the morphology method. Typically this is a value of 1.
     /// * `kernel_info`: An array of doubles representing the morphology kernel.
     pub fn morphology_image(
-        &self,
+        &mut self,
         morphology_method: MorphologyMethod,
         iterations: isize,
         kernel_info: &KernelInfo,
@@ -1497,6 +2216,93 @@ impl MagickWand {
         })
     }
 
+    /// Detects edges with the Canny algorithm, leaving a binary image of
+    /// one pixel wide edges.
+    ///
+    /// * `radius`, `sigma`: the Gaussian smoothing applied first.
+    /// * `lower`, `upper`: hysteresis thresholds as fractions of the strongest
+    ///   gradient, typically around 0.1 and 0.3. Edges above `upper` are kept,
+    ///   as are edges above `lower` connected to them.
+    pub fn canny_edge_image(
+        &mut self,
+        radius: f64,
+        sigma: f64,
+        lower: f64,
+        upper: f64,
+    ) -> Result<()> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickCannyEdgeImage(self.wand, radius, sigma, lower, upper)
+        })
+    }
+
+    /// Finds straight lines in a binary edge image, such as the output of
+    /// [`canny_edge_image`](Self::canny_edge_image), with the Hough transform.
+    /// The image is replaced by a rendering of the lines, which are also
+    /// returned.
+    ///
+    /// * `width`, `height`: the neighborhood in which a line must be the
+    ///   local maximum of the accumulator.
+    /// * `threshold`: the minimum number of edge pixels on a line.
+    pub fn hough_line_image(
+        &mut self,
+        width: usize,
+        height: usize,
+        threshold: usize,
+    ) -> Result<Vec<HoughLine>> {
+        self.result_from_boolean(unsafe {
+            bindings::MagickHoughLineImage(self.wand, width, height, threshold)
+        })?;
+        // The lines image is read from MVG, which the MVG coder keeps around.
+        let mvg = self.get_image_artifact("mvg:vector-graphics")?;
+        Ok(HoughLine::parse_mvg(&mvg))
+    }
+
+    /// Labels each connected region of same-colored pixels, replacing the
+    /// image by one where every pixel holds the id of its component, and
+    /// returns the components.
+    ///
+    /// * `connectivity`: 4 to connect pixels only horizontally and
+    ///   vertically, or 8 to also connect them diagonally.
+    ///
+    /// Components can be filtered with artifacts such as
+    /// `connected-components:area-threshold` (see
+    /// [`set_image_artifact`](Self::set_image_artifact)); merged components
+    /// are not returned.
+    ///
+    /// Requires ImageMagick 7.1 or later.
+    #[cfg(magick_7_1_0_0)]
+    pub fn connected_components_image(&mut self, connectivity: usize) -> Result<Vec<Component>> {
+        let mut objects: *mut bindings::CCObjectInfo = ptr::null_mut();
+        self.result_from_boolean(unsafe {
+            bindings::MagickConnectedComponentsImage(self.wand, connectivity, &mut objects)
+        })?;
+        if objects.is_null() {
+            return Err(MagickError(
+                "connected components returned no objects".to_string(),
+            ));
+        }
+        // The labeled image has one colormap entry per object.
+        let image = unsafe { bindings::GetImageFromMagickWand(self.wand) };
+        let (colors, pixels) = if image.is_null() {
+            (0, 0)
+        } else {
+            unsafe { ((*image).colors, (*image).columns * (*image).rows) }
+        };
+        let count = colors.min(pixels).min(bindings::MaxColormapSize as usize);
+        // SAFETY: ConnectedComponentsImage allocates (and zeroes) one object
+        // per pixel of the image, since every pixel may be its own component,
+        // and fails with "too many objects" beyond `MaxColormapSize` labels.
+        // The labeled image keeps the size of the input, so `count` never
+        // exceeds the allocation, even if `colors` were not the label count.
+        let components = unsafe { slice::from_raw_parts(objects, count) }
+            .iter()
+            .filter(|object| object.census > 0.0 && object.merge == MagickFalse)
+            .map(Component::from_object)
+            .collect();
+        unsafe { bindings::MagickRelinquishMemory(objects as *mut c_void) };
+        Ok(components)
+    }
+
     /// Apply color transformation to an image. The method permits saturation changes, hue rotation,
     /// luminance to alpha, and various other effects. Although variable-sized transformation
     /// matrices can be used, typically one uses a 5x5 matrix for an RGBA image and a 6x6 for CMYKA
@@ -1505,7 +2311,7 @@ impl MagickWand {
     /// (divide Flash offset by 255).
     ///
     /// * `color_matrix`: the color matrix.
-    pub fn color_matrix_image(&self, color_matrix: &KernelInfo) -> Result<()> {
+    pub fn color_matrix_image(&mut self, color_matrix: &KernelInfo) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickColorMatrixImage(self.wand, color_matrix.get_ptr())
         })
@@ -1540,6 +2346,56 @@ impl MagickWand {
         self.result_from_ptr(wand_ptr, MagickWand::from_ptr)
     }
 
+    /// Applies the discrete Fourier transform to the current image, returning
+    /// a new wand with two images: the magnitude and phase if `magnitude` is
+    /// true, otherwise the real and imaginary parts.
+    ///
+    /// Fourier transforms need ImageMagick built with the FFTW delegate, and
+    /// the frequency domain values are only preserved faithfully with HDRI.
+    pub fn forward_fourier_transform_image(&self, magnitude: bool) -> Result<MagickWand> {
+        require_fftw()?;
+        let wand = MagickWand::new_from_image(&self.get_image()?)?;
+        wand.result_from_boolean(unsafe {
+            bindings::MagickForwardFourierTransformImage(wand.wand, magnitude.into())
+        })?;
+        Ok(wand)
+    }
+
+    /// Applies the inverse discrete Fourier transform, treating the current
+    /// image as the magnitude (or real part) and the current image of
+    /// `phase_wand` as the phase (or imaginary part), and returns the spatial
+    /// domain image in a new wand.
+    ///
+    /// * `magnitude`: true if the images are magnitude and phase, false if
+    ///   they are real and imaginary parts, as produced by
+    ///   [`forward_fourier_transform_image`](Self::forward_fourier_transform_image).
+    pub fn inverse_fourier_transform_image(
+        &self,
+        phase_wand: &MagickWand,
+        magnitude: bool,
+    ) -> Result<MagickWand> {
+        require_fftw()?;
+        let wand = MagickWand::new_from_image(&self.get_image()?)?;
+        wand.result_from_boolean(unsafe {
+            bindings::MagickInverseFourierTransformImage(
+                wand.wand,
+                phase_wand.wand,
+                magnitude.into(),
+            )
+        })?;
+        Ok(wand)
+    }
+
+    /// Performs complex arithmetic on the image sequence, where each pair of
+    /// images holds the real and imaginary parts of a complex image, and
+    /// returns the result in a new wand.
+    ///
+    /// * `operator`: the complex operator.
+    pub fn complex_images(&self, operator: ComplexOperator) -> Result<MagickWand> {
+        let wand_ptr = unsafe { bindings::MagickComplexImages(self.wand, operator) };
+        self.result_from_ptr(wand_ptr, MagickWand::from_ptr)
+    }
+
     /// Returns the current image from the magick wand.
     pub fn get_image(&self) -> Result<Image<'_>> {
         self.result_from_ptr(
@@ -1549,7 +2405,7 @@ impl MagickWand {
     }
 
     /// Enhances contrast of an image by stretching the range of intensity values.
-    pub fn contrast_stretch_image(&self, black_point: f64, white_point: f64) -> Result<()> {
+    pub fn contrast_stretch_image(&mut self, black_point: f64, white_point: f64) -> Result<()> {
         self.result_from_boolean(unsafe {
             bindings::MagickContrastStretchImage(self.wand, black_point, white_point)
         })
@@ -1572,6 +2428,9 @@ impl MagickWand {
         MagickUniqueImageColors => unique_image_colors()
 
         /// Applies k-means color reduction to the image.
+        ///
+        /// Requires ImageMagick 7.0.10-37 or later.
+        #[cfg(magick_7_0_10_37)]
         MagickKmeansImage => kmeans(number_colors: usize, max_iterations: usize, tolerance: f64)
 
         /// Extracts the 'mean' from the image and adjust the image to try make set its gamma appropriately.
@@ -1579,6 +2438,41 @@ impl MagickWand {
 
         /// Adjusts the levels of a particular image channel by scaling the minimum and maximum values to the full quantum range.
         MagickAutoLevelImage => auto_level()
+
+        /// Simulates a charcoal drawing. `radius` is the radius of the Gaussian
+        /// in pixels, not counting the center pixel (0 picks a suitable one),
+        /// and `sigma` its standard deviation.
+        MagickCharcoalImage => charcoal_image(radius: f64, sigma: f64)
+
+        /// Simulates a pencil sketch, with strokes of the given `radius` and
+        /// `sigma` drawn at `angle` degrees.
+        MagickSketchImage => sketch_image(radius: f64, sigma: f64, angle: f64)
+
+        /// Simulates an oil painting: each pixel is replaced by the most
+        /// frequent color within `radius`.
+        MagickOilPaintImage => oil_paint_image(radius: f64, sigma: f64)
+
+        /// Swirls the pixels about the center of the image by up to `degrees`.
+        MagickSwirlImage => swirl_image(degrees: f64, method: PixelInterpolateMethod)
+
+        /// Shifts each column of pixels along a sine wave of the given
+        /// `amplitude` and `wave_length`. The image grows by twice the
+        /// amplitude; the new area is filled with the background color.
+        MagickWaveImage => wave_image(amplitude: f64, wave_length: f64, method: PixelInterpolateMethod)
+
+        /// Softens the edges of the image in vignette style, blending into the
+        /// background color. `x` and `y` define the size of the unaffected
+        /// ellipse and `radius` and `sigma` the blur of its edge.
+        MagickVignetteImage => vignette_image(radius: f64, sigma: f64, x: isize, y: isize)
+
+        /// Gives the image a three-dimensional effect with a grayscale
+        /// embossing of the given `radius` and `sigma`.
+        MagickEmbossImage => emboss_image(radius: f64, sigma: f64)
+
+        /// Shines a distant light on the image, lit from `azimuth` degrees
+        /// around and `elevation` degrees above the surface, to create a
+        /// three-dimensional effect. With `gray`, only the shading is kept.
+        MagickShadeImage => shade_image(gray: bool, azimuth: f64, elevation: f64)
     );
 
     get!(get_image_colors, MagickGetImageColors, usize);
@@ -1702,6 +2596,23 @@ impl MagickWand {
     }
 }
 
+/// Wand option through which `render_caption` and `render_label` hand the
+/// text to the `caption:` and `label:` formats.
+const TEXT_OPTION: &str = "magick-rust:text";
+
+/// Fail with a clear error when ImageMagick lacks the FFTW delegate, instead of
+/// the generic failure of the Fourier transform operations.
+fn require_fftw() -> Result<()> {
+    if crate::magick_version()?.fftw() {
+        Ok(())
+    } else {
+        Err(MagickError(
+            "ImageMagick was built without the FFTW delegate required for Fourier transforms"
+                .to_string(),
+        ))
+    }
+}
+
 impl fmt::Debug for MagickWand {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f, "MagickWand {{")?;
@@ -15,12 +15,21 @@
  */
 #[macro_use]
 mod macros;
+#[cfg(feature = "tokio")]
+mod async_wand;
 mod drawing;
+mod frozen;
 mod magick;
+mod mvg;
+mod path;
 mod pixel;
 
-pub use self::drawing::DrawingWand;
+#[cfg(feature = "tokio")]
+pub use self::async_wand::{AsyncMagickWand, CancellationToken, default_limiter};
+pub use self::drawing::{DrawingWand, GraphicContext};
+pub use self::frozen::FrozenWand;
 pub use self::magick::MagickWand;
+pub use self::path::PathBuilder;
 pub use self::pixel::{HSL, PixelWand};
 
 use crate::bindings::MagickBooleanType::MagickFalse;
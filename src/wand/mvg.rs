@@ -0,0 +1,1363 @@
+/*
+ * Copyright 2026 Nathan Fiedler
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::slice;
+
+use libc::c_void;
+
+use crate::bindings;
+use crate::result::MagickError;
+use crate::result::Result;
+use crate::{
+    AffineMatrix, AlignType, ClipPathUnits, CompositeOperator, DecorationType, DirectionType,
+    FillRule, GravityType, LineCap, LineJoin, PaintMethod, StretchType, StyleType,
+};
+
+use super::path::parse_svg_path;
+use super::{DrawingWand, MagickWand, PixelWand};
+
+impl DrawingWand {
+    /// Parse `mvg`, a script in ImageMagick's Magick Vector Graphics language,
+    /// and replay it as draw calls on this wand, so that it can be rendered
+    /// with [`MagickWand::draw_image`] like any other drawing. The whole
+    /// script is parsed before anything is drawn, so nothing is drawn if it is
+    /// malformed.
+    ///
+    /// Shapes, paths, text, colors, transforms, graphic contexts, patterns and
+    /// clip paths are supported, as are the stroke, fill and font settings. Embedded
+    /// images must be files or `data:` URLs.
+    ///
+    /// ```no_run
+    /// use magick_rust::DrawingWand;
+    ///
+    /// let mut drawing = DrawingWand::new();
+    /// drawing
+    ///     .load_mvg(
+    ///         "fill '#3060C0' stroke white stroke-width 2
+    ///          circle 50,50 50,10
+    ///          text 20,95 'Hello'",
+    ///     )
+    ///     .unwrap();
+    /// ```
+    pub fn load_mvg(&mut self, mvg: &str) -> Result<()> {
+        let commands = parse_mvg(mvg)?;
+        for command in &commands {
+            command.apply(self)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the MVG script recorded by this wand so far; this is the
+    /// content of [`get_vector_graphics`](Self::get_vector_graphics) without
+    /// its XML wrapper.
+    pub fn get_mvg(&self) -> Result<String> {
+        let xml = self.get_vector_graphics()?;
+        let content = match xml.find("<vector-graphics>") {
+            Some(start) => {
+                let content = &xml[start + "<vector-graphics>".len()..];
+                let end = content
+                    .find("</vector-graphics>")
+                    .ok_or("malformed vector graphics")?;
+                &content[..end]
+            }
+            None => "",
+        };
+        Ok(unescape_xml(content))
+    }
+
+    /// Serialize what has been drawn with this wand as a standalone SVG
+    /// document. The recorded primitives are exported with their fill,
+    /// stroke, font and transform settings, and patterns and clip paths
+    /// become their SVG counterparts.
+    /// Settings that have no SVG equivalent, such as gravity and kerning, are
+    /// left out, as are [`draw_color`](Self::draw_color) flood fills.
+    pub fn to_svg(&self) -> Result<String> {
+        let commands = parse_mvg(&self.get_mvg()?)?;
+        let mut svg = SvgWriter::new();
+        for command in &commands {
+            svg.write(command)?;
+        }
+        Ok(svg.finish())
+    }
+}
+
+/// How a shape is filled or stroked.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Paint {
+    Color(String),
+    Pattern(String),
+}
+
+/// One MVG command.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum MvgCommand {
+    PushGraphicContext,
+    PopGraphicContext,
+    PushPattern(String, (f64, f64, f64, f64)),
+    PopPattern,
+    PushClipPath(String),
+    PopClipPath,
+    PushDefs,
+    PopDefs,
+    Affine(AffineMatrix),
+    Translate(f64, f64),
+    Rotate(f64),
+    Scale(f64, f64),
+    SkewX(f64),
+    SkewY(f64),
+    Viewbox(f64, f64, f64, f64),
+    Fill(Paint),
+    FillOpacity(f64),
+    FillRule(FillRule),
+    Stroke(Paint),
+    StrokeOpacity(f64),
+    StrokeWidth(f64),
+    StrokeDashArray(Vec<f64>),
+    StrokeDashOffset(f64),
+    StrokeLineCap(LineCap),
+    StrokeLineJoin(LineJoin),
+    StrokeMiterLimit(f64),
+    StrokeAntialias(bool),
+    Opacity(f64),
+    ClipPath(String),
+    ClipRule(FillRule),
+    ClipUnits(ClipPathUnits),
+    Font(String),
+    FontFamily(String),
+    FontSize(f64),
+    FontStyle(StyleType),
+    FontWeight(usize),
+    FontStretch(StretchType),
+    TextAlign(AlignType),
+    TextAntialias(bool),
+    TextUnderColor(String),
+    Decorate(DecorationType),
+    Gravity(GravityType),
+    Kerning(f64),
+    InterlineSpacing(f64),
+    InterwordSpacing(f64),
+    Direction(DirectionType),
+    Encoding(String),
+    Density(String),
+    BorderColor(String),
+    Point(f64, f64),
+    Line(f64, f64, f64, f64),
+    Rectangle(f64, f64, f64, f64),
+    RoundRectangle(f64, f64, f64, f64, f64, f64),
+    Arc(f64, f64, f64, f64, f64, f64),
+    Ellipse(f64, f64, f64, f64, f64, f64),
+    Circle(f64, f64, f64, f64),
+    Polyline(Vec<(f64, f64)>),
+    Polygon(Vec<(f64, f64)>),
+    Bezier(Vec<(f64, f64)>),
+    Path(String),
+    Text(f64, f64, String),
+    Color(f64, f64, PaintMethod),
+    Image(CompositeOperator, (f64, f64, f64, f64), String),
+}
+
+const ALIGNMENTS: &[(&str, AlignType)] = &[
+    ("left", AlignType::Left),
+    ("start", AlignType::Left),
+    ("center", AlignType::Center),
+    ("middle", AlignType::Center),
+    ("right", AlignType::Right),
+    ("end", AlignType::Right),
+];
+
+const CLIP_UNITS: &[(&str, ClipPathUnits)] = &[
+    ("userspace", ClipPathUnits::UserSpace),
+    ("userspaceonuse", ClipPathUnits::UserSpaceOnUse),
+    ("objectboundingbox", ClipPathUnits::ObjectBoundingBox),
+];
+
+const COMPOSE_OPERATORS: &[(&str, CompositeOperator)] = &[
+    ("over", CompositeOperator::Over),
+    ("srcover", CompositeOperator::SrcOver),
+    ("copy", CompositeOperator::Copy),
+    ("src", CompositeOperator::Src),
+    ("dstover", CompositeOperator::DstOver),
+    ("in", CompositeOperator::In),
+    ("srcin", CompositeOperator::SrcIn),
+    ("out", CompositeOperator::Out),
+    ("srcout", CompositeOperator::SrcOut),
+    ("atop", CompositeOperator::Atop),
+    ("srcatop", CompositeOperator::SrcAtop),
+    ("dstin", CompositeOperator::DstIn),
+    ("dstout", CompositeOperator::DstOut),
+    ("dstatop", CompositeOperator::DstAtop),
+    ("xor", CompositeOperator::Xor),
+    ("clear", CompositeOperator::Clear),
+    ("multiply", CompositeOperator::Multiply),
+    ("screen", CompositeOperator::Screen),
+    ("overlay", CompositeOperator::Overlay),
+    ("plus", CompositeOperator::Plus),
+    ("darken", CompositeOperator::Darken),
+    ("lighten", CompositeOperator::Lighten),
+    ("difference", CompositeOperator::Difference),
+];
+
+const DECORATIONS: &[(&str, DecorationType)] = &[
+    ("none", DecorationType::No),
+    ("underline", DecorationType::Underline),
+    ("overline", DecorationType::Overline),
+    ("linethrough", DecorationType::LineThrough),
+];
+
+const DIRECTIONS: &[(&str, DirectionType)] = &[
+    ("righttoleft", DirectionType::RightToLeft),
+    ("lefttoright", DirectionType::LeftToRight),
+    ("toptobottom", DirectionType::TopToBottom),
+];
+
+const FILL_RULES: &[(&str, FillRule)] = &[
+    ("evenodd", FillRule::EvenOdd),
+    ("nonzero", FillRule::NonZero),
+];
+
+const GRAVITIES: &[(&str, GravityType)] = &[
+    ("northwest", GravityType::NorthWest),
+    ("north", GravityType::North),
+    ("northeast", GravityType::NorthEast),
+    ("west", GravityType::West),
+    ("center", GravityType::Center),
+    ("east", GravityType::East),
+    ("southwest", GravityType::SouthWest),
+    ("south", GravityType::South),
+    ("southeast", GravityType::SouthEast),
+];
+
+const LINE_CAPS: &[(&str, LineCap)] = &[
+    ("butt", LineCap::Butt),
+    ("round", LineCap::Round),
+    ("square", LineCap::Square),
+];
+
+const LINE_JOINS: &[(&str, LineJoin)] = &[
+    ("miter", LineJoin::Miter),
+    ("round", LineJoin::Round),
+    ("bevel", LineJoin::Bevel),
+];
+
+const PAINT_METHODS: &[(&str, PaintMethod)] = &[
+    ("point", PaintMethod::Point),
+    ("replace", PaintMethod::Replace),
+    ("floodfill", PaintMethod::Floodfill),
+    ("filltoborder", PaintMethod::FillToBorder),
+    ("reset", PaintMethod::Reset),
+];
+
+const STRETCHES: &[(&str, StretchType)] = &[
+    ("normal", StretchType::Normal),
+    ("ultracondensed", StretchType::UltraCondensed),
+    ("extracondensed", StretchType::ExtraCondensed),
+    ("condensed", StretchType::Condensed),
+    ("semicondensed", StretchType::SemiCondensed),
+    ("semiexpanded", StretchType::SemiExpanded),
+    ("expanded", StretchType::Expanded),
+    ("extraexpanded", StretchType::ExtraExpanded),
+    ("ultraexpanded", StretchType::UltraExpanded),
+    ("any", StretchType::Any),
+];
+
+const STYLES: &[(&str, StyleType)] = &[
+    ("normal", StyleType::Normal),
+    ("italic", StyleType::Italic),
+    ("oblique", StyleType::Oblique),
+    ("any", StyleType::Any),
+];
+
+/// Reads the keywords and arguments of an MVG script.
+struct Tokens<'a> {
+    text: &'a str,
+    pos: usize,
+    line: usize,
+}
+
+impl Tokens<'_> {
+    fn error(&self, message: &str) -> MagickError {
+        MagickError(format!("invalid MVG at line {}: {message}", self.line))
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_separators(&mut self) {
+        while let Some(c) = self.peek() {
+            if !c.is_ascii_whitespace() && c != b',' {
+                break;
+            }
+            if c == b'\n' {
+                self.line += 1;
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// The next keyword in lower case, skipping comments, or `None` at the
+    /// end of the script.
+    fn keyword(&mut self) -> Result<Option<String>> {
+        loop {
+            self.skip_separators();
+            match self.peek() {
+                None => return Ok(None),
+                Some(b'#') => {
+                    while self.peek().is_some_and(|c| c != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                Some(_) => return Ok(Some(self.token()?.to_ascii_lowercase())),
+            }
+        }
+    }
+
+    /// The next argument: a quoted string (`'...'`, `"..."` or `{...}`), or
+    /// a bare word that runs to the next separator, such as a number or
+    /// `rgb(255, 0, 0)`.
+    fn token(&mut self) -> Result<String> {
+        self.skip_separators();
+        let bytes = self.text.as_bytes();
+        match self.peek() {
+            None => Err(self.error("unexpected end of script")),
+            Some(quote @ (b'\'' | b'"' | b'{')) => {
+                let close = if quote == b'{' { b'}' } else { quote };
+                self.pos += 1;
+                let mut value = Vec::new();
+                loop {
+                    match self.peek() {
+                        None => return Err(self.error("unterminated string")),
+                        Some(c) if c == close => break,
+                        Some(b'\\') if quote != b'{' && self.pos + 1 < bytes.len() => {
+                            self.pos += 1;
+                            value.push(bytes[self.pos]);
+                        }
+                        Some(c) => {
+                            if c == b'\n' {
+                                self.line += 1;
+                            }
+                            value.push(c);
+                        }
+                    }
+                    self.pos += 1;
+                }
+                self.pos += 1;
+                Ok(String::from_utf8_lossy(&value).into_owned())
+            }
+            Some(_) => {
+                let start = self.pos;
+                let mut depth = 0usize;
+                while let Some(c) = self.peek() {
+                    match c {
+                        b'(' => depth += 1,
+                        b')' => depth = depth.saturating_sub(1),
+                        b'\n' if depth > 0 => self.line += 1,
+                        c if depth == 0 && (c.is_ascii_whitespace() || c == b',') => break,
+                        _ => {}
+                    }
+                    self.pos += 1;
+                }
+                Ok(self.text[start..self.pos].to_string())
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<f64> {
+        let token = self.token()?;
+        token
+            .parse()
+            .map_err(|_| self.error(&format!("expected a number, found '{token}'")))
+    }
+
+    fn numbers<const N: usize>(&mut self) -> Result<[f64; N]> {
+        let mut numbers = [0.0; N];
+        for number in &mut numbers {
+            *number = self.number()?;
+        }
+        Ok(numbers)
+    }
+
+    /// A number that may also be given as a percentage.
+    fn opacity(&mut self) -> Result<f64> {
+        let token = self.token()?;
+        let (value, scale) = match token.strip_suffix('%') {
+            Some(value) => (value, 0.01),
+            None => (token.as_str(), 1.0),
+        };
+        value
+            .parse::<f64>()
+            .map(|value| value * scale)
+            .map_err(|_| self.error(&format!("expected an opacity, found '{token}'")))
+    }
+
+    fn boolean(&mut self) -> Result<bool> {
+        Ok(self.number()? != 0.0)
+    }
+
+    fn at_number(&mut self) -> bool {
+        self.skip_separators();
+        let rest = &self.text.as_bytes()[self.pos..];
+        let starts_number = |c: &u8| c.is_ascii_digit() || *c == b'.';
+        match rest.first() {
+            Some(b'-' | b'+') => rest.get(1).is_some_and(starts_number),
+            Some(c) => starts_number(c),
+            None => false,
+        }
+    }
+
+    fn points(&mut self) -> Result<Vec<(f64, f64)>> {
+        let mut points = Vec::new();
+        while self.at_number() {
+            let [x, y] = self.numbers()?;
+            points.push((x, y));
+        }
+        if points.is_empty() {
+            return Err(self.error("expected a list of points"));
+        }
+        Ok(points)
+    }
+
+    fn paint(&mut self) -> Result<Paint> {
+        let token = self.token()?;
+        match token.strip_prefix("url(") {
+            Some(url) => url
+                .strip_suffix(')')
+                .map(|id| Paint::Pattern(id.trim().trim_start_matches('#').to_string()))
+                .ok_or_else(|| self.error(&format!("malformed url '{token}'"))),
+            None => Ok(Paint::Color(token)),
+        }
+    }
+
+    fn value<T: Copy>(&mut self, table: &[(&str, T)], what: &str) -> Result<T> {
+        let token = self.token()?;
+        lookup(table, &token).ok_or_else(|| self.error(&format!("unknown {what} '{token}'")))
+    }
+}
+
+/// Find `name` in `table`, ignoring case, dashes and underscores, so that both
+/// `LineThrough` and `line-through` are accepted.
+fn lookup<T: Copy>(table: &[(&str, T)], name: &str) -> Option<T> {
+    let name: String = name
+        .chars()
+        .filter(|c| *c != '-' && *c != '_')
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    table
+        .iter()
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| *value)
+}
+
+pub(crate) fn parse_mvg(mvg: &str) -> Result<Vec<MvgCommand>> {
+    use MvgCommand::*;
+
+    let mut tokens = Tokens {
+        text: mvg,
+        pos: 0,
+        line: 1,
+    };
+    let mut commands = Vec::new();
+    while let Some(keyword) = tokens.keyword()? {
+        let command = match keyword.as_str() {
+            "push" | "pop" => {
+                let what = tokens.token()?.to_ascii_lowercase();
+                match (keyword.as_str(), what.as_str()) {
+                    ("push", "graphic-context") => PushGraphicContext,
+                    ("pop", "graphic-context") => PopGraphicContext,
+                    ("push", "pattern") => {
+                        let id = tokens.token()?;
+                        let [x, y, width, height] = tokens.numbers()?;
+                        PushPattern(id, (x, y, width, height))
+                    }
+                    ("pop", "pattern") => PopPattern,
+                    ("push", "clip-path") => PushClipPath(tokens.token()?),
+                    ("pop", "clip-path") => PopClipPath,
+                    ("push", "defs") => PushDefs,
+                    ("pop", "defs") => PopDefs,
+                    _ => return Err(tokens.error(&format!("unsupported '{keyword} {what}'"))),
+                }
+            }
+            "affine" => {
+                let [sx, rx, ry, sy, tx, ty] = tokens.numbers()?;
+                Affine(AffineMatrix {
+                    sx,
+                    rx,
+                    ry,
+                    sy,
+                    tx,
+                    ty,
+                })
+            }
+            "translate" => {
+                let [x, y] = tokens.numbers()?;
+                Translate(x, y)
+            }
+            "rotate" => Rotate(tokens.number()?),
+            "scale" => {
+                let [x, y] = tokens.numbers()?;
+                Scale(x, y)
+            }
+            "skewx" => SkewX(tokens.number()?),
+            "skewy" => SkewY(tokens.number()?),
+            "viewbox" => {
+                let [x1, y1, x2, y2] = tokens.numbers()?;
+                Viewbox(x1, y1, x2, y2)
+            }
+            "fill" => Fill(tokens.paint()?),
+            "fill-opacity" => FillOpacity(tokens.opacity()?),
+            "fill-rule" => FillRule(tokens.value(FILL_RULES, "fill rule")?),
+            "stroke" => Stroke(tokens.paint()?),
+            "stroke-opacity" => StrokeOpacity(tokens.opacity()?),
+            "stroke-width" => StrokeWidth(tokens.number()?),
+            "stroke-dasharray" => {
+                if tokens.at_number() {
+                    let mut dashes = Vec::new();
+                    while tokens.at_number() {
+                        dashes.push(tokens.number()?);
+                    }
+                    StrokeDashArray(dashes)
+                } else if tokens.token()?.eq_ignore_ascii_case("none") {
+                    StrokeDashArray(Vec::new())
+                } else {
+                    return Err(tokens.error("expected a dash array or 'none'"));
+                }
+            }
+            "stroke-dashoffset" => StrokeDashOffset(tokens.number()?),
+            "stroke-linecap" => StrokeLineCap(tokens.value(LINE_CAPS, "line cap")?),
+            "stroke-linejoin" => StrokeLineJoin(tokens.value(LINE_JOINS, "line join")?),
+            "stroke-miterlimit" => StrokeMiterLimit(tokens.number()?),
+            "stroke-antialias" => StrokeAntialias(tokens.boolean()?),
+            "opacity" => Opacity(tokens.opacity()?),
+            "clip-path" => match tokens.paint()? {
+                Paint::Pattern(id) | Paint::Color(id) => ClipPath(id),
+            },
+            "clip-rule" => ClipRule(tokens.value(FILL_RULES, "clip rule")?),
+            "clip-units" => ClipUnits(tokens.value(CLIP_UNITS, "clip units")?),
+            "font" => Font(tokens.token()?),
+            "font-family" => FontFamily(tokens.token()?),
+            "font-size" => FontSize(tokens.number()?),
+            "font-style" => FontStyle(tokens.value(STYLES, "font style")?),
+            "font-weight" => {
+                let token = tokens.token()?;
+                FontWeight(match token.to_ascii_lowercase().as_str() {
+                    "normal" => 400,
+                    "bold" => 700,
+                    weight => weight
+                        .parse::<f64>()
+                        .map_err(|_| tokens.error(&format!("unknown font weight '{token}'")))?
+                        as usize,
+                })
+            }
+            "font-stretch" => FontStretch(tokens.value(STRETCHES, "font stretch")?),
+            "text-align" | "text-anchor" => TextAlign(tokens.value(ALIGNMENTS, "alignment")?),
+            "text-antialias" => TextAntialias(tokens.boolean()?),
+            "text-undercolor" => TextUnderColor(tokens.token()?),
+            "decorate" => Decorate(tokens.value(DECORATIONS, "decoration")?),
+            "gravity" => Gravity(tokens.value(GRAVITIES, "gravity")?),
+            "kerning" => Kerning(tokens.number()?),
+            "interline-spacing" => InterlineSpacing(tokens.number()?),
+            "interword-spacing" => InterwordSpacing(tokens.number()?),
+            "direction" => Direction(tokens.value(DIRECTIONS, "direction")?),
+            "encoding" => Encoding(tokens.token()?),
+            "density" => Density(tokens.token()?),
+            "border-color" => BorderColor(tokens.token()?),
+            "point" => {
+                let [x, y] = tokens.numbers()?;
+                Point(x, y)
+            }
+            "line" => {
+                let [x1, y1, x2, y2] = tokens.numbers()?;
+                Line(x1, y1, x2, y2)
+            }
+            "rectangle" => {
+                let [x1, y1, x2, y2] = tokens.numbers()?;
+                Rectangle(x1, y1, x2, y2)
+            }
+            "roundrectangle" => {
+                let [x1, y1, x2, y2, rx, ry] = tokens.numbers()?;
+                RoundRectangle(x1, y1, x2, y2, rx, ry)
+            }
+            "arc" => {
+                let [x1, y1, x2, y2, start, end] = tokens.numbers()?;
+                Arc(x1, y1, x2, y2, start, end)
+            }
+            "ellipse" => {
+                let [x, y, rx, ry, start, end] = tokens.numbers()?;
+                Ellipse(x, y, rx, ry, start, end)
+            }
+            "circle" => {
+                let [x, y, px, py] = tokens.numbers()?;
+                Circle(x, y, px, py)
+            }
+            "polyline" => Polyline(tokens.points()?),
+            "polygon" => Polygon(tokens.points()?),
+            "bezier" => Bezier(tokens.points()?),
+            "path" => {
+                let data = tokens.token()?;
+                parse_svg_path(&data).map_err(|error| tokens.error(&error.0))?;
+                Path(data)
+            }
+            "text" => {
+                let [x, y] = tokens.numbers()?;
+                Text(x, y, tokens.token()?)
+            }
+            "color" => {
+                let [x, y] = tokens.numbers()?;
+                Color(x, y, tokens.value(PAINT_METHODS, "paint method")?)
+            }
+            "image" => {
+                let compose = tokens.value(COMPOSE_OPERATORS, "compose operator")?;
+                let [x, y, width, height] = tokens.numbers()?;
+                Image(compose, (x, y, width, height), tokens.token()?)
+            }
+            _ => return Err(tokens.error(&format!("unsupported keyword '{keyword}'"))),
+        };
+        commands.push(command);
+    }
+    Ok(commands)
+}
+
+fn pixel_wand(color: &str) -> Result<PixelWand> {
+    let mut pixel = PixelWand::new();
+    pixel.set_color(color)?;
+    Ok(pixel)
+}
+
+/// Read an image referenced by an MVG `image` command, either a file or a
+/// `data:` URL.
+fn read_image_source(source: &str) -> Result<MagickWand> {
+    let mut image = MagickWand::new();
+    if source.starts_with("data:") {
+        image.read_image(&format!("inline:{source}"))?;
+    } else {
+        image.read_image(source)?;
+    }
+    Ok(image)
+}
+
+impl MvgCommand {
+    fn apply(&self, wand: &mut DrawingWand) -> Result<()> {
+        use MvgCommand::*;
+
+        match self {
+            PushGraphicContext => wand.push_graphic_context()?,
+            PopGraphicContext => wand.pop_graphic_context()?,
+            PushPattern(id, bounds) => wand.push_pattern(id, *bounds)?,
+            PopPattern => wand.pop_pattern()?,
+            PushClipPath(id) => wand.push_clip_path(id)?,
+            PopClipPath => wand.pop_clip_path(),
+            PushDefs => unsafe { bindings::DrawPushDefs(wand.as_ptr()) },
+            PopDefs => unsafe { bindings::DrawPopDefs(wand.as_ptr()) },
+            Affine(matrix) => wand.affine(matrix),
+            Translate(x, y) => wand.translate(*x, *y),
+            Rotate(degrees) => wand.rotate(*degrees),
+            Scale(x, y) => wand.scale(*x, *y),
+            SkewX(degrees) => wand.skew_x(*degrees),
+            SkewY(degrees) => wand.skew_y(*degrees),
+            Viewbox(x1, y1, x2, y2) => wand.set_viewbox(*x1, *y1, *x2, *y2),
+            Fill(Paint::Color(color)) => wand.set_fill_color(&pixel_wand(color)?),
+            Fill(Paint::Pattern(id)) => wand.set_fill_pattern(id)?,
+            FillOpacity(opacity) => wand.set_fill_opacity(*opacity),
+            FillRule(rule) => wand.set_fill_rule(*rule),
+            Stroke(Paint::Color(color)) => wand.set_stroke_color(&pixel_wand(color)?),
+            Stroke(Paint::Pattern(id)) => wand.set_stroke_pattern(id)?,
+            StrokeOpacity(opacity) => wand.set_stroke_opacity(*opacity),
+            StrokeWidth(width) => wand.set_stroke_width(*width),
+            StrokeDashArray(dashes) => wand.set_stroke_dash_array(dashes)?,
+            StrokeDashOffset(offset) => wand.set_stroke_dash_offset(*offset),
+            StrokeLineCap(cap) => wand.set_stroke_line_cap(*cap),
+            StrokeLineJoin(join) => wand.set_stroke_line_join(*join),
+            StrokeMiterLimit(limit) => wand.set_stroke_miter_limit(*limit as usize),
+            StrokeAntialias(antialias) => wand.set_stroke_antialias((*antialias).into()),
+            Opacity(opacity) => wand.set_opacity(*opacity),
+            ClipPath(id) => wand.set_clip_path(id)?,
+            ClipRule(rule) => wand.set_clip_rule(*rule),
+            ClipUnits(units) => wand.set_clip_units(*units),
+            Font(font) => wand.set_font(font)?,
+            FontFamily(family) => wand.set_font_family(family)?,
+            FontSize(size) => wand.set_font_size(*size),
+            FontStyle(style) => wand.set_font_style(*style),
+            FontWeight(weight) => wand.set_font_weight(*weight),
+            FontStretch(stretch) => wand.set_font_stretch(*stretch),
+            TextAlign(alignment) => wand.set_text_alignment(*alignment),
+            TextAntialias(antialias) => wand.set_text_antialias((*antialias).into()),
+            TextUnderColor(color) => wand.set_text_under_color(&pixel_wand(color)?),
+            Decorate(decoration) => wand.set_text_decoration(*decoration),
+            Gravity(gravity) => wand.set_gravity(*gravity),
+            Kerning(kerning) => wand.set_text_kerning(*kerning),
+            InterlineSpacing(spacing) => wand.set_text_interline_spacing(*spacing),
+            InterwordSpacing(spacing) => wand.set_text_interword_spacing(*spacing),
+            Direction(direction) => wand.set_text_direction(*direction),
+            Encoding(encoding) => wand.set_text_encoding(encoding)?,
+            Density(density) => wand.set_density(density)?,
+            BorderColor(color) => wand.set_border_color(&pixel_wand(color)?),
+            Point(x, y) => wand.draw_point(*x, *y),
+            Line(x1, y1, x2, y2) => wand.draw_line(*x1, *y1, *x2, *y2),
+            Rectangle(x1, y1, x2, y2) => wand.draw_rectangle(*x1, *y1, *x2, *y2),
+            RoundRectangle(x1, y1, x2, y2, rx, ry) => {
+                wand.draw_round_rectangle(*x1, *y1, *x2, *y2, *rx, *ry)
+            }
+            Arc(x1, y1, x2, y2, start, end) => wand.draw_arc(*x1, *y1, *x2, *y2, *start, *end),
+            Ellipse(x, y, rx, ry, start, end) => wand.draw_ellipse(*x, *y, *rx, *ry, *start, *end),
+            Circle(x, y, px, py) => wand.draw_circle(*x, *y, *px, *py),
+            Polyline(points) => wand.draw_polyline(points),
+            Polygon(points) => wand.draw_polygon(points),
+            Bezier(points) => wand.draw_bezier(points),
+            Path(data) => wand.draw_svg_path(data)?,
+            Text(x, y, text) => wand.draw_annotation(*x, *y, text)?,
+            Color(x, y, method) => wand.draw_color(*x, *y, *method),
+            Image(compose, (x, y, width, height), source) => {
+                let image = read_image_source(source)?;
+                wand.draw_composite(*compose, *x, *y, *width, *height, &image)?
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The SVG presentation attributes in effect while exporting.
+#[derive(Debug, Clone)]
+struct SvgStyle {
+    fill: String,
+    fill_alpha: f64,
+    fill_opacity: f64,
+    fill_rule: Option<&'static str>,
+    stroke: String,
+    stroke_alpha: f64,
+    stroke_opacity: f64,
+    stroke_width: f64,
+    dash_array: Vec<f64>,
+    dash_offset: f64,
+    line_cap: Option<&'static str>,
+    line_join: Option<&'static str>,
+    miter_limit: Option<f64>,
+    opacity: f64,
+    clip_path: Option<String>,
+    clip_rule: Option<&'static str>,
+    clip_units: Option<&'static str>,
+    font_family: Option<String>,
+    font_size: f64,
+    font_style: Option<&'static str>,
+    font_weight: Option<usize>,
+    font_stretch: Option<&'static str>,
+    text_anchor: Option<&'static str>,
+    text_decoration: Option<&'static str>,
+    transform: AffineMatrix,
+}
+
+impl Default for SvgStyle {
+    fn default() -> Self {
+        SvgStyle {
+            fill: "rgb(0,0,0)".to_string(),
+            fill_alpha: 1.0,
+            fill_opacity: 1.0,
+            fill_rule: None,
+            stroke: "none".to_string(),
+            stroke_alpha: 1.0,
+            stroke_opacity: 1.0,
+            stroke_width: 1.0,
+            dash_array: Vec::new(),
+            dash_offset: 0.0,
+            line_cap: None,
+            line_join: None,
+            miter_limit: None,
+            opacity: 1.0,
+            clip_path: None,
+            clip_rule: None,
+            clip_units: None,
+            font_family: None,
+            font_size: 12.0,
+            font_style: None,
+            font_weight: None,
+            font_stretch: None,
+            text_anchor: None,
+            text_decoration: None,
+            transform: AffineMatrix::IDENTITY,
+        }
+    }
+}
+
+/// Which presentation attributes apply to an element.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum ElementKind {
+    Shape,
+    Text,
+    Image,
+}
+
+/// Builds an SVG document from MVG commands.
+struct SvgWriter {
+    style: SvgStyle,
+    saved_styles: Vec<SvgStyle>,
+    viewbox: Option<(f64, f64, f64, f64)>,
+    defs: String,
+    body: String,
+    definitions: Vec<PendingDefinition>,
+}
+
+/// A pattern or clip path being defined, with the body its definition
+/// interrupted.
+struct PendingDefinition {
+    tag: &'static str,
+    start: String,
+    body: String,
+}
+
+impl SvgWriter {
+    fn new() -> Self {
+        SvgWriter {
+            style: SvgStyle::default(),
+            saved_styles: Vec::new(),
+            viewbox: None,
+            defs: String::new(),
+            body: String::new(),
+            definitions: Vec::new(),
+        }
+    }
+
+    fn write(&mut self, command: &MvgCommand) -> Result<()> {
+        use MvgCommand::*;
+
+        match command {
+            PushGraphicContext => self.saved_styles.push(self.style.clone()),
+            PopGraphicContext => self.pop_style()?,
+            PushPattern(id, (x, y, width, height)) => self.push_definition(
+                "pattern",
+                format!(
+                    "<pattern id=\"{}\" x=\"{x}\" y=\"{y}\" width=\"{width}\" height=\"{height}\" patternUnits=\"userSpaceOnUse\">",
+                    escape_xml(id)
+                ),
+            ),
+            PopPattern => self.pop_definition("pattern")?,
+            PushClipPath(id) => {
+                let units = self.style.clip_units.unwrap_or("userSpaceOnUse");
+                self.push_definition(
+                    "clipPath",
+                    format!(
+                        "<clipPath id=\"{}\" clipPathUnits=\"{units}\">",
+                        escape_xml(id)
+                    ),
+                )
+            }
+            PopClipPath => self.pop_definition("clipPath")?,
+            Affine(matrix) => self.transform(*matrix),
+            Translate(x, y) => self.transform(AffineMatrix::translation(*x, *y)),
+            Rotate(degrees) => self.transform(AffineMatrix::rotation(*degrees)),
+            Scale(x, y) => self.transform(AffineMatrix::scaling(*x, *y)),
+            SkewX(degrees) => self.transform(AffineMatrix::skew_x(*degrees)),
+            SkewY(degrees) => self.transform(AffineMatrix::skew_y(*degrees)),
+            Viewbox(x1, y1, x2, y2) => self.viewbox = Some((*x1, *y1, *x2, *y2)),
+            Fill(paint) => (self.style.fill, self.style.fill_alpha) = svg_paint(paint)?,
+            FillOpacity(opacity) => self.style.fill_opacity = *opacity,
+            FillRule(rule) => {
+                self.style.fill_rule = match rule {
+                    crate::FillRule::EvenOdd => Some("evenodd"),
+                    crate::FillRule::NonZero => Some("nonzero"),
+                    _ => None,
+                }
+            }
+            Stroke(paint) => (self.style.stroke, self.style.stroke_alpha) = svg_paint(paint)?,
+            StrokeOpacity(opacity) => self.style.stroke_opacity = *opacity,
+            StrokeWidth(width) => self.style.stroke_width = *width,
+            StrokeDashArray(dashes) => self.style.dash_array = dashes.clone(),
+            StrokeDashOffset(offset) => self.style.dash_offset = *offset,
+            StrokeLineCap(cap) => {
+                self.style.line_cap = match cap {
+                    LineCap::Butt => Some("butt"),
+                    LineCap::Round => Some("round"),
+                    LineCap::Square => Some("square"),
+                    _ => None,
+                }
+            }
+            StrokeLineJoin(join) => {
+                self.style.line_join = match join {
+                    LineJoin::Miter => Some("miter"),
+                    LineJoin::Round => Some("round"),
+                    LineJoin::Bevel => Some("bevel"),
+                    _ => None,
+                }
+            }
+            StrokeMiterLimit(limit) => self.style.miter_limit = Some(*limit),
+            Opacity(opacity) => self.style.opacity = *opacity,
+            ClipPath(id) => self.style.clip_path = Some(id.clone()),
+            ClipRule(rule) => {
+                self.style.clip_rule = match rule {
+                    crate::FillRule::EvenOdd => Some("evenodd"),
+                    crate::FillRule::NonZero => Some("nonzero"),
+                    _ => None,
+                }
+            }
+            ClipUnits(units) => {
+                self.style.clip_units = match units {
+                    ClipPathUnits::UserSpace | ClipPathUnits::UserSpaceOnUse => {
+                        Some("userSpaceOnUse")
+                    }
+                    ClipPathUnits::ObjectBoundingBox => Some("objectBoundingBox"),
+                    _ => None,
+                }
+            }
+            Font(family) | FontFamily(family) => self.style.font_family = Some(family.clone()),
+            FontSize(size) => self.style.font_size = *size,
+            FontStyle(style) => {
+                self.style.font_style = match style {
+                    StyleType::Normal => Some("normal"),
+                    StyleType::Italic => Some("italic"),
+                    StyleType::Oblique => Some("oblique"),
+                    _ => None,
+                }
+            }
+            FontWeight(weight) => self.style.font_weight = Some(*weight),
+            FontStretch(stretch) => {
+                self.style.font_stretch = match stretch {
+                    StretchType::Normal => Some("normal"),
+                    StretchType::UltraCondensed => Some("ultra-condensed"),
+                    StretchType::ExtraCondensed => Some("extra-condensed"),
+                    StretchType::Condensed => Some("condensed"),
+                    StretchType::SemiCondensed => Some("semi-condensed"),
+                    StretchType::SemiExpanded => Some("semi-expanded"),
+                    StretchType::Expanded => Some("expanded"),
+                    StretchType::ExtraExpanded => Some("extra-expanded"),
+                    StretchType::UltraExpanded => Some("ultra-expanded"),
+                    _ => None,
+                }
+            }
+            TextAlign(alignment) => {
+                self.style.text_anchor = match alignment {
+                    AlignType::Left => Some("start"),
+                    AlignType::Center => Some("middle"),
+                    AlignType::Right => Some("end"),
+                    _ => None,
+                }
+            }
+            Decorate(decoration) => {
+                self.style.text_decoration = match decoration {
+                    DecorationType::Underline => Some("underline"),
+                    DecorationType::Overline => Some("overline"),
+                    DecorationType::LineThrough => Some("line-through"),
+                    _ => None,
+                }
+            }
+            Point(x, y) => self.element(
+                "rect",
+                &format!("x=\"{x}\" y=\"{y}\" width=\"1\" height=\"1\""),
+                ElementKind::Shape,
+                None,
+            ),
+            Line(x1, y1, x2, y2) => self.element(
+                "line",
+                &format!("x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\""),
+                ElementKind::Shape,
+                None,
+            ),
+            Rectangle(x1, y1, x2, y2) => {
+                self.element("rect", &rect(*x1, *y1, *x2, *y2), ElementKind::Shape, None)
+            }
+            RoundRectangle(x1, y1, x2, y2, rx, ry) => self.element(
+                "rect",
+                &format!("{} rx=\"{rx}\" ry=\"{ry}\"", rect(*x1, *y1, *x2, *y2)),
+                ElementKind::Shape,
+                None,
+            ),
+            Arc(x1, y1, x2, y2, start, end) => self.ellipse(
+                (x1 + x2) / 2.0,
+                (y1 + y2) / 2.0,
+                (x2 - x1).abs() / 2.0,
+                (y2 - y1).abs() / 2.0,
+                *start,
+                *end,
+            ),
+            Ellipse(x, y, rx, ry, start, end) => self.ellipse(*x, *y, *rx, *ry, *start, *end),
+            Circle(x, y, px, py) => self.element(
+                "circle",
+                &format!("cx=\"{x}\" cy=\"{y}\" r=\"{}\"", (px - x).hypot(py - y)),
+                ElementKind::Shape,
+                None,
+            ),
+            Polyline(points) => self.element(
+                "polyline",
+                &format!("points=\"{}\"", svg_points(points)),
+                ElementKind::Shape,
+                None,
+            ),
+            Polygon(points) => self.element(
+                "polygon",
+                &format!("points=\"{}\"", svg_points(points)),
+                ElementKind::Shape,
+                None,
+            ),
+            Bezier(points) => self.element(
+                "path",
+                &format!("d=\"{}\"", bezier_path(points)),
+                ElementKind::Shape,
+                None,
+            ),
+            Path(data) => self.element(
+                "path",
+                &format!("d=\"{}\"", escape_xml(data.trim())),
+                ElementKind::Shape,
+                None,
+            ),
+            Text(x, y, text) => self.element(
+                "text",
+                &format!("x=\"{x}\" y=\"{y}\""),
+                ElementKind::Text,
+                Some(text),
+            ),
+            Color(x, y, _) => self
+                .body
+                .push_str(&format!("<!-- color fill at {x},{y} omitted -->\n")),
+            Image(_, (x, y, width, height), source) => {
+                let mut image = read_image_source(source)?;
+                let (width, height) = if *width == 0.0 && *height == 0.0 {
+                    (
+                        image.get_image_width() as f64,
+                        image.get_image_height() as f64,
+                    )
+                } else {
+                    (*width, *height)
+                };
+                let png = base64(&image.write_image_blob("png")?)?;
+                self.element(
+                    "image",
+                    &format!(
+                        "x=\"{x}\" y=\"{y}\" width=\"{width}\" height=\"{height}\" xlink:href=\"data:image/png;base64,{png}\""
+                    ),
+                    ElementKind::Image,
+                    None,
+                )
+            }
+            PushDefs | PopDefs | StrokeAntialias(_)
+            | TextAntialias(_) | TextUnderColor(_) | Gravity(_) | Kerning(_)
+            | InterlineSpacing(_) | InterwordSpacing(_) | Direction(_) | Encoding(_)
+            | Density(_) | BorderColor(_) => {}
+        }
+        Ok(())
+    }
+
+    /// Start collecting the content of a pattern or clip path, drawn with
+    /// the current style but in its own coordinate system.
+    fn push_definition(&mut self, tag: &'static str, start: String) {
+        self.saved_styles.push(self.style.clone());
+        self.style.transform = AffineMatrix::IDENTITY;
+        self.style.clip_path = None;
+        self.definitions.push(PendingDefinition {
+            tag,
+            start,
+            body: std::mem::take(&mut self.body),
+        });
+    }
+
+    fn pop_definition(&mut self, tag: &'static str) -> Result<()> {
+        let definition = self
+            .definitions
+            .pop()
+            .filter(|definition| definition.tag == tag)
+            .ok_or_else(|| MagickError(format!("unbalanced {tag} definition")))?;
+        let content = std::mem::replace(&mut self.body, definition.body);
+        self.defs
+            .push_str(&format!("{}\n{content}</{tag}>\n", definition.start));
+        self.pop_style()
+    }
+
+    fn pop_style(&mut self) -> Result<()> {
+        self.style = self
+            .saved_styles
+            .pop()
+            .ok_or("pop graphic-context without push graphic-context")?;
+        Ok(())
+    }
+
+    fn transform(&mut self, matrix: AffineMatrix) {
+        self.style.transform = matrix.then(&self.style.transform);
+    }
+
+    /// Write an elliptical arc from `start` to `end` degrees, or a whole
+    /// ellipse.
+    fn ellipse(&mut self, x: f64, y: f64, rx: f64, ry: f64, start: f64, end: f64) {
+        if (end - start).abs() >= 360.0 {
+            self.element(
+                "ellipse",
+                &format!("cx=\"{x}\" cy=\"{y}\" rx=\"{rx}\" ry=\"{ry}\""),
+                ElementKind::Shape,
+                None,
+            );
+        } else {
+            let point = |degrees: f64| {
+                let radians = degrees.to_radians();
+                (x + rx * radians.cos(), y + ry * radians.sin())
+            };
+            let (x1, y1) = point(start);
+            let (x2, y2) = point(end);
+            let large_arc = u8::from((end - start).rem_euclid(360.0) > 180.0);
+            self.element(
+                "path",
+                &format!("d=\"M{x1},{y1} A{rx},{ry} 0 {large_arc} 1 {x2},{y2}\""),
+                ElementKind::Shape,
+                None,
+            );
+        }
+    }
+
+    fn element(&mut self, name: &str, geometry: &str, kind: ElementKind, text: Option<&str>) {
+        let style = &self.style;
+        let mut element = format!("<{name} {geometry}");
+        if kind != ElementKind::Image {
+            element.push_str(&format!(" fill=\"{}\"", escape_xml(&style.fill)));
+            let fill_opacity = style.fill_alpha * style.fill_opacity;
+            if fill_opacity != 1.0 {
+                element.push_str(&format!(" fill-opacity=\"{fill_opacity}\""));
+            }
+            if let Some(rule) = style.fill_rule {
+                element.push_str(&format!(" fill-rule=\"{rule}\""));
+            }
+            if style.stroke != "none" {
+                element.push_str(&format!(
+                    " stroke=\"{}\" stroke-width=\"{}\"",
+                    escape_xml(&style.stroke),
+                    style.stroke_width
+                ));
+                let stroke_opacity = style.stroke_alpha * style.stroke_opacity;
+                if stroke_opacity != 1.0 {
+                    element.push_str(&format!(" stroke-opacity=\"{stroke_opacity}\""));
+                }
+                if !style.dash_array.is_empty() {
+                    let dashes: Vec<String> = style
+                        .dash_array
+                        .iter()
+                        .map(|dash| dash.to_string())
+                        .collect();
+                    element.push_str(&format!(" stroke-dasharray=\"{}\"", dashes.join(",")));
+                }
+                if style.dash_offset != 0.0 {
+                    element.push_str(&format!(" stroke-dashoffset=\"{}\"", style.dash_offset));
+                }
+                if let Some(cap) = style.line_cap {
+                    element.push_str(&format!(" stroke-linecap=\"{cap}\""));
+                }
+                if let Some(join) = style.line_join {
+                    element.push_str(&format!(" stroke-linejoin=\"{join}\""));
+                }
+                if let Some(limit) = style.miter_limit {
+                    element.push_str(&format!(" stroke-miterlimit=\"{limit}\""));
+                }
+            }
+        }
+        if kind == ElementKind::Text {
+            if let Some(family) = &style.font_family {
+                element.push_str(&format!(" font-family=\"{}\"", escape_xml(family)));
+            }
+            element.push_str(&format!(" font-size=\"{}\"", style.font_size));
+            if let Some(font_style) = style.font_style {
+                element.push_str(&format!(" font-style=\"{font_style}\""));
+            }
+            if let Some(weight) = style.font_weight {
+                element.push_str(&format!(" font-weight=\"{weight}\""));
+            }
+            if let Some(stretch) = style.font_stretch {
+                element.push_str(&format!(" font-stretch=\"{stretch}\""));
+            }
+            if let Some(anchor) = style.text_anchor {
+                element.push_str(&format!(" text-anchor=\"{anchor}\""));
+            }
+            if let Some(decoration) = style.text_decoration {
+                element.push_str(&format!(" text-decoration=\"{decoration}\""));
+            }
+        }
+        if style.opacity != 1.0 {
+            element.push_str(&format!(" opacity=\"{}\"", style.opacity));
+        }
+        if let Some(rule) = style.clip_rule {
+            element.push_str(&format!(" clip-rule=\"{rule}\""));
+        }
+        if let Some(clip_path) = &style.clip_path {
+            element.push_str(&format!(" clip-path=\"url(#{})\"", escape_xml(clip_path)));
+        }
+        let matrix = style.transform;
+        if matrix != AffineMatrix::IDENTITY {
+            element.push_str(&format!(
+                " transform=\"matrix({} {} {} {} {} {})\"",
+                matrix.sx, matrix.rx, matrix.ry, matrix.sy, matrix.tx, matrix.ty
+            ));
+        }
+        match text {
+            Some(text) => element.push_str(&format!(">{}</{name}>\n", escape_xml(text))),
+            None => element.push_str("/>\n"),
+        }
+        self.body.push_str(&element);
+    }
+
+    fn finish(self) -> String {
+        let mut svg = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\" \
+             xmlns:xlink=\"http://www.w3.org/1999/xlink\"",
+        );
+        if let Some((x1, y1, x2, y2)) = self.viewbox {
+            let (width, height) = (x2 - x1, y2 - y1);
+            svg.push_str(&format!(
+                " width=\"{width}\" height=\"{height}\" viewBox=\"{x1} {y1} {width} {height}\""
+            ));
+        }
+        svg.push_str(">\n");
+        if !self.defs.is_empty() {
+            svg.push_str(&format!("<defs>\n{}</defs>\n", self.defs));
+        }
+        svg.push_str(&self.body);
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// The SVG paint and alpha of an MVG fill or stroke.
+fn svg_paint(paint: &Paint) -> Result<(String, f64)> {
+    match paint {
+        Paint::Pattern(id) => Ok((format!("url(#{id})"), 1.0)),
+        Paint::Color(color) => {
+            let pixel = pixel_wand(color)?;
+            let alpha = pixel.get_alpha();
+            if alpha <= 0.0 {
+                return Ok(("none".to_string(), 1.0));
+            }
+            let channel = |value: f64| (value * 255.0).round().clamp(0.0, 255.0) as u8;
+            Ok((
+                format!(
+                    "rgb({},{},{})",
+                    channel(pixel.get_red()),
+                    channel(pixel.get_green()),
+                    channel(pixel.get_blue())
+                ),
+                alpha,
+            ))
+        }
+    }
+}
+
+fn rect(x1: f64, y1: f64, x2: f64, y2: f64) -> String {
+    format!(
+        "x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"",
+        x1.min(x2),
+        y1.min(y2),
+        (x2 - x1).abs(),
+        (y2 - y1).abs()
+    )
+}
+
+fn svg_points(points: &[(f64, f64)]) -> String {
+    let points: Vec<String> = points.iter().map(|(x, y)| format!("{x},{y}")).collect();
+    points.join(" ")
+}
+
+/// SVG path data for a Bezier curve of any degree; curves of degree three or
+/// less map onto path commands, higher degrees are approximated by lines.
+fn bezier_path(points: &[(f64, f64)]) -> String {
+    const SEGMENTS: usize = 64;
+    let (&(x, y), rest) = points.split_first().expect("bezier without points");
+    match rest.len() {
+        0 => format!("M{x},{y}"),
+        1 => format!("M{x},{y} L{}", svg_points(rest)),
+        2 => format!("M{x},{y} Q{}", svg_points(rest)),
+        3 => format!("M{x},{y} C{}", svg_points(rest)),
+        _ => {
+            let samples: Vec<(f64, f64)> = (1..=SEGMENTS)
+                .map(|i| bezier_point(points, i as f64 / SEGMENTS as f64))
+                .collect();
+            format!("M{x},{y} L{}", svg_points(&samples))
+        }
+    }
+}
+
+fn bezier_point(points: &[(f64, f64)], t: f64) -> (f64, f64) {
+    let mut points = points.to_vec();
+    while points.len() > 1 {
+        points = points
+            .windows(2)
+            .map(|pair| {
+                (
+                    pair[0].0 + (pair[1].0 - pair[0].0) * t,
+                    pair[0].1 + (pair[1].1 - pair[0].1) * t,
+                )
+            })
+            .collect();
+    }
+    points[0]
+}
+
+fn base64(data: &[u8]) -> Result<String> {
+    let mut length = 0;
+    let encoded = unsafe { bindings::Base64Encode(data.as_ptr(), data.len(), &mut length) };
+    if encoded.is_null() {
+        return Err(MagickError("could not encode image data".to_string()));
+    }
+    let result =
+        String::from_utf8_lossy(unsafe { slice::from_raw_parts(encoded as *const u8, length) })
+            .into_owned();
+    unsafe { bindings::RelinquishMagickMemory(encoded as *mut c_void) };
+    Ok(result)
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape_xml(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest
+            .find(';')
+            .and_then(|end| decode_entity(&rest[1..end]).map(|c| (c, end)));
+        match entity {
+            Some((c, end)) => {
+                unescaped.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        _ => {
+            let code = match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => name.strip_prefix('#')?.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
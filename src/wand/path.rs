@@ -0,0 +1,478 @@
+/*
+ * Copyright 2026 Nathan Fiedler
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use crate::bindings;
+use crate::result::MagickError;
+use crate::result::Result;
+
+use super::DrawingWand;
+
+/// Builds a path on a [`DrawingWand`], created with [`DrawingWand::path`].
+///
+/// Every command exists in an absolute form, whose coordinates are in the
+/// drawing's user space, and a `_relative` form, whose coordinates are offsets
+/// from the current point. The commands mirror those of SVG path data; see
+/// [`DrawingWand::draw_svg_path`] to draw such data directly.
+///
+/// The path is finished, and drawn with the current fill and stroke settings,
+/// when the builder is dropped (or when [`finish`](Self::finish) is called).
+///
+/// ```no_run
+/// use magick_rust::DrawingWand;
+///
+/// let mut drawing = DrawingWand::new();
+/// drawing
+///     .path()
+///     .move_to(10.0, 10.0)
+///     .line_to(90.0, 10.0)
+///     .arc_to(40.0, 40.0, 0.0, false, true, 10.0, 10.0)
+///     .close();
+/// ```
+pub struct PathBuilder<'a> {
+    drawing_wand: &'a mut DrawingWand,
+}
+
+impl PathBuilder<'_> {
+    fn wand(&self) -> *mut bindings::DrawingWand {
+        self.drawing_wand.as_ptr()
+    }
+
+    /// Start a new subpath at `(x, y)`.
+    pub fn move_to(&mut self, x: f64, y: f64) -> &mut Self {
+        unsafe { bindings::DrawPathMoveToAbsolute(self.wand(), x, y) };
+        self
+    }
+
+    /// Start a new subpath offset `(x, y)` from the current point.
+    pub fn move_to_relative(&mut self, x: f64, y: f64) -> &mut Self {
+        unsafe { bindings::DrawPathMoveToRelative(self.wand(), x, y) };
+        self
+    }
+
+    /// Draw a line from the current point to `(x, y)`.
+    pub fn line_to(&mut self, x: f64, y: f64) -> &mut Self {
+        unsafe { bindings::DrawPathLineToAbsolute(self.wand(), x, y) };
+        self
+    }
+
+    /// Draw a line from the current point to the point offset by `(x, y)`.
+    pub fn line_to_relative(&mut self, x: f64, y: f64) -> &mut Self {
+        unsafe { bindings::DrawPathLineToRelative(self.wand(), x, y) };
+        self
+    }
+
+    /// Draw a horizontal line from the current point to the column `x`.
+    pub fn horizontal_line_to(&mut self, x: f64) -> &mut Self {
+        unsafe { bindings::DrawPathLineToHorizontalAbsolute(self.wand(), x) };
+        self
+    }
+
+    /// Draw a horizontal line of length `x` from the current point.
+    pub fn horizontal_line_to_relative(&mut self, x: f64) -> &mut Self {
+        unsafe { bindings::DrawPathLineToHorizontalRelative(self.wand(), x) };
+        self
+    }
+
+    /// Draw a vertical line from the current point to the row `y`.
+    pub fn vertical_line_to(&mut self, y: f64) -> &mut Self {
+        unsafe { bindings::DrawPathLineToVerticalAbsolute(self.wand(), y) };
+        self
+    }
+
+    /// Draw a vertical line of length `y` from the current point.
+    pub fn vertical_line_to_relative(&mut self, y: f64) -> &mut Self {
+        unsafe { bindings::DrawPathLineToVerticalRelative(self.wand(), y) };
+        self
+    }
+
+    /// Draw a cubic Bezier curve to `(x, y)`, with `(x1, y1)` the control
+    /// point at the start and `(x2, y2)` the one at the end of the curve.
+    pub fn curve_to(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, x: f64, y: f64) -> &mut Self {
+        unsafe { bindings::DrawPathCurveToAbsolute(self.wand(), x1, y1, x2, y2, x, y) };
+        self
+    }
+
+    /// Relative form of [`curve_to`](Self::curve_to).
+    pub fn curve_to_relative(
+        &mut self,
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+        x: f64,
+        y: f64,
+    ) -> &mut Self {
+        unsafe { bindings::DrawPathCurveToRelative(self.wand(), x1, y1, x2, y2, x, y) };
+        self
+    }
+
+    /// Draw a cubic Bezier curve to `(x, y)` whose first control point is the
+    /// reflection of the previous curve's second control point.
+    pub fn smooth_curve_to(&mut self, x2: f64, y2: f64, x: f64, y: f64) -> &mut Self {
+        unsafe { bindings::DrawPathCurveToSmoothAbsolute(self.wand(), x2, y2, x, y) };
+        self
+    }
+
+    /// Relative form of [`smooth_curve_to`](Self::smooth_curve_to).
+    pub fn smooth_curve_to_relative(&mut self, x2: f64, y2: f64, x: f64, y: f64) -> &mut Self {
+        unsafe { bindings::DrawPathCurveToSmoothRelative(self.wand(), x2, y2, x, y) };
+        self
+    }
+
+    /// Draw a quadratic Bezier curve to `(x, y)` with control point
+    /// `(x1, y1)`.
+    pub fn quadratic_curve_to(&mut self, x1: f64, y1: f64, x: f64, y: f64) -> &mut Self {
+        unsafe { bindings::DrawPathCurveToQuadraticBezierAbsolute(self.wand(), x1, y1, x, y) };
+        self
+    }
+
+    /// Relative form of [`quadratic_curve_to`](Self::quadratic_curve_to).
+    pub fn quadratic_curve_to_relative(&mut self, x1: f64, y1: f64, x: f64, y: f64) -> &mut Self {
+        unsafe { bindings::DrawPathCurveToQuadraticBezierRelative(self.wand(), x1, y1, x, y) };
+        self
+    }
+
+    /// Draw a quadratic Bezier curve to `(x, y)` whose control point is the
+    /// reflection of the previous curve's control point.
+    pub fn smooth_quadratic_curve_to(&mut self, x: f64, y: f64) -> &mut Self {
+        unsafe { bindings::DrawPathCurveToQuadraticBezierSmoothAbsolute(self.wand(), x, y) };
+        self
+    }
+
+    /// Relative form of
+    /// [`smooth_quadratic_curve_to`](Self::smooth_quadratic_curve_to).
+    pub fn smooth_quadratic_curve_to_relative(&mut self, x: f64, y: f64) -> &mut Self {
+        unsafe { bindings::DrawPathCurveToQuadraticBezierSmoothRelative(self.wand(), x, y) };
+        self
+    }
+
+    /// Draw an elliptical arc to `(x, y)` with radii `rx` and `ry`, the
+    /// ellipse rotated by `x_axis_rotation` degrees. Of the four candidate
+    /// arcs, `large_arc` selects the one spanning more than 180 degrees and
+    /// `sweep` the one drawn in the positive-angle direction.
+    #[allow(clippy::too_many_arguments)]
+    pub fn arc_to(
+        &mut self,
+        rx: f64,
+        ry: f64,
+        x_axis_rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        x: f64,
+        y: f64,
+    ) -> &mut Self {
+        unsafe {
+            bindings::DrawPathEllipticArcAbsolute(
+                self.wand(),
+                rx,
+                ry,
+                x_axis_rotation,
+                large_arc.into(),
+                sweep.into(),
+                x,
+                y,
+            )
+        };
+        self
+    }
+
+    /// Relative form of [`arc_to`](Self::arc_to).
+    #[allow(clippy::too_many_arguments)]
+    pub fn arc_to_relative(
+        &mut self,
+        rx: f64,
+        ry: f64,
+        x_axis_rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        x: f64,
+        y: f64,
+    ) -> &mut Self {
+        unsafe {
+            bindings::DrawPathEllipticArcRelative(
+                self.wand(),
+                rx,
+                ry,
+                x_axis_rotation,
+                large_arc.into(),
+                sweep.into(),
+                x,
+                y,
+            )
+        };
+        self
+    }
+
+    /// Close the current subpath with a line back to its starting point.
+    pub fn close(&mut self) -> &mut Self {
+        unsafe { bindings::DrawPathClose(self.wand()) };
+        self
+    }
+
+    /// Finish the path. This is the same as dropping the builder.
+    pub fn finish(self) {}
+
+    fn apply(&mut self, command: &PathCommand) {
+        match *command {
+            PathCommand::MoveTo(relative, x, y) if relative => self.move_to_relative(x, y),
+            PathCommand::MoveTo(_, x, y) => self.move_to(x, y),
+            PathCommand::LineTo(relative, x, y) if relative => self.line_to_relative(x, y),
+            PathCommand::LineTo(_, x, y) => self.line_to(x, y),
+            PathCommand::HorizontalLineTo(relative, x) if relative => {
+                self.horizontal_line_to_relative(x)
+            }
+            PathCommand::HorizontalLineTo(_, x) => self.horizontal_line_to(x),
+            PathCommand::VerticalLineTo(relative, y) if relative => {
+                self.vertical_line_to_relative(y)
+            }
+            PathCommand::VerticalLineTo(_, y) => self.vertical_line_to(y),
+            PathCommand::CurveTo(relative, x1, y1, x2, y2, x, y) if relative => {
+                self.curve_to_relative(x1, y1, x2, y2, x, y)
+            }
+            PathCommand::CurveTo(_, x1, y1, x2, y2, x, y) => self.curve_to(x1, y1, x2, y2, x, y),
+            PathCommand::SmoothCurveTo(relative, x2, y2, x, y) if relative => {
+                self.smooth_curve_to_relative(x2, y2, x, y)
+            }
+            PathCommand::SmoothCurveTo(_, x2, y2, x, y) => self.smooth_curve_to(x2, y2, x, y),
+            PathCommand::QuadraticCurveTo(relative, x1, y1, x, y) if relative => {
+                self.quadratic_curve_to_relative(x1, y1, x, y)
+            }
+            PathCommand::QuadraticCurveTo(_, x1, y1, x, y) => self.quadratic_curve_to(x1, y1, x, y),
+            PathCommand::SmoothQuadraticCurveTo(relative, x, y) if relative => {
+                self.smooth_quadratic_curve_to_relative(x, y)
+            }
+            PathCommand::SmoothQuadraticCurveTo(_, x, y) => self.smooth_quadratic_curve_to(x, y),
+            PathCommand::ArcTo(relative, rx, ry, rotation, large_arc, sweep, x, y) if relative => {
+                self.arc_to_relative(rx, ry, rotation, large_arc, sweep, x, y)
+            }
+            PathCommand::ArcTo(_, rx, ry, rotation, large_arc, sweep, x, y) => {
+                self.arc_to(rx, ry, rotation, large_arc, sweep, x, y)
+            }
+            PathCommand::Close => self.close(),
+        };
+    }
+}
+
+impl Drop for PathBuilder<'_> {
+    fn drop(&mut self) {
+        unsafe { bindings::DrawPathFinish(self.wand()) };
+    }
+}
+
+impl DrawingWand {
+    /// Start a path; see [`PathBuilder`].
+    pub fn path(&mut self) -> PathBuilder<'_> {
+        unsafe { bindings::DrawPathStart(self.as_ptr()) };
+        PathBuilder { drawing_wand: self }
+    }
+
+    /// Draw a path given as SVG path data (the `d` attribute of an SVG `path`
+    /// element), such as `"M10 10 h80 v80 h-80 Z"`. All commands are
+    /// supported, including implicit repetition and compact number syntax.
+    /// Nothing is drawn if the data is malformed.
+    pub fn draw_svg_path(&mut self, data: &str) -> Result<()> {
+        let commands = parse_svg_path(data)?;
+        let mut path = self.path();
+        for command in &commands {
+            path.apply(command);
+        }
+        Ok(())
+    }
+}
+
+/// One path command; the first field of each variant marks the relative form.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum PathCommand {
+    MoveTo(bool, f64, f64),
+    LineTo(bool, f64, f64),
+    HorizontalLineTo(bool, f64),
+    VerticalLineTo(bool, f64),
+    CurveTo(bool, f64, f64, f64, f64, f64, f64),
+    SmoothCurveTo(bool, f64, f64, f64, f64),
+    QuadraticCurveTo(bool, f64, f64, f64, f64),
+    SmoothQuadraticCurveTo(bool, f64, f64),
+    ArcTo(bool, f64, f64, f64, bool, bool, f64, f64),
+    Close,
+}
+
+/// Reads the numbers and flags of SVG path data.
+struct PathData<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl PathData<'_> {
+    fn error(&self, message: &str) -> MagickError {
+        MagickError(format!(
+            "invalid path data at offset {}: {message}",
+            self.pos
+        ))
+    }
+
+    fn skip_separators(&mut self) {
+        while self
+            .data
+            .get(self.pos)
+            .is_some_and(|c| c.is_ascii_whitespace() || *c == b',')
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.skip_separators();
+        self.pos >= self.data.len()
+    }
+
+    fn command(&mut self) -> Option<u8> {
+        self.skip_separators();
+        let c = *self.data.get(self.pos)?;
+        if c.is_ascii_alphabetic() && c != b'e' && c != b'E' {
+            self.pos += 1;
+            Some(c)
+        } else {
+            None
+        }
+    }
+
+    fn digits(&mut self) -> usize {
+        let start = self.pos;
+        while self.data.get(self.pos).is_some_and(u8::is_ascii_digit) {
+            self.pos += 1;
+        }
+        self.pos - start
+    }
+
+    fn number(&mut self) -> Result<f64> {
+        self.skip_separators();
+        let start = self.pos;
+        if matches!(self.data.get(self.pos), Some(b'+' | b'-')) {
+            self.pos += 1;
+        }
+        let mut count = self.digits();
+        if self.data.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            count += self.digits();
+        }
+        if count == 0 {
+            self.pos = start;
+            return Err(self.error("expected a number"));
+        }
+        if matches!(self.data.get(self.pos), Some(b'e' | b'E')) {
+            let mantissa_end = self.pos;
+            self.pos += 1;
+            if matches!(self.data.get(self.pos), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if self.digits() == 0 {
+                self.pos = mantissa_end;
+            }
+        }
+        let text = std::str::from_utf8(&self.data[start..self.pos]).unwrap_or_default();
+        text.parse().map_err(|_| self.error("expected a number"))
+    }
+
+    fn flag(&mut self) -> Result<bool> {
+        self.skip_separators();
+        match self.data.get(self.pos) {
+            Some(b'0') => {
+                self.pos += 1;
+                Ok(false)
+            }
+            Some(b'1') => {
+                self.pos += 1;
+                Ok(true)
+            }
+            _ => Err(self.error("expected an arc flag (0 or 1)")),
+        }
+    }
+
+    fn pair(&mut self) -> Result<(f64, f64)> {
+        Ok((self.number()?, self.number()?))
+    }
+}
+
+/// Parse SVG path data into path commands.
+pub(crate) fn parse_svg_path(data: &str) -> Result<Vec<PathCommand>> {
+    let mut input = PathData {
+        data: data.as_bytes(),
+        pos: 0,
+    };
+    let mut commands = Vec::new();
+    let mut current = None;
+    while !input.at_end() {
+        let letter = match input.command() {
+            Some(letter) => letter,
+            // Parameters without a command letter repeat the previous command.
+            None => current.ok_or_else(|| input.error("expected a command"))?,
+        };
+        let relative = letter.is_ascii_lowercase();
+        let command = match letter.to_ascii_uppercase() {
+            b'M' => {
+                let (x, y) = input.pair()?;
+                // Further pairs after a move are implicit line commands.
+                current = Some(if relative { b'l' } else { b'L' });
+                commands.push(PathCommand::MoveTo(relative, x, y));
+                continue;
+            }
+            b'L' => {
+                let (x, y) = input.pair()?;
+                PathCommand::LineTo(relative, x, y)
+            }
+            b'H' => PathCommand::HorizontalLineTo(relative, input.number()?),
+            b'V' => PathCommand::VerticalLineTo(relative, input.number()?),
+            b'C' => {
+                let (x1, y1) = input.pair()?;
+                let (x2, y2) = input.pair()?;
+                let (x, y) = input.pair()?;
+                PathCommand::CurveTo(relative, x1, y1, x2, y2, x, y)
+            }
+            b'S' => {
+                let (x2, y2) = input.pair()?;
+                let (x, y) = input.pair()?;
+                PathCommand::SmoothCurveTo(relative, x2, y2, x, y)
+            }
+            b'Q' => {
+                let (x1, y1) = input.pair()?;
+                let (x, y) = input.pair()?;
+                PathCommand::QuadraticCurveTo(relative, x1, y1, x, y)
+            }
+            b'T' => {
+                let (x, y) = input.pair()?;
+                PathCommand::SmoothQuadraticCurveTo(relative, x, y)
+            }
+            b'A' => {
+                let (rx, ry) = input.pair()?;
+                let rotation = input.number()?;
+                let large_arc = input.flag()?;
+                let sweep = input.flag()?;
+                let (x, y) = input.pair()?;
+                PathCommand::ArcTo(relative, rx, ry, rotation, large_arc, sweep, x, y)
+            }
+            b'Z' => {
+                current = None;
+                commands.push(PathCommand::Close);
+                continue;
+            }
+            _ => {
+                input.pos -= 1;
+                return Err(input.error("unknown command"));
+            }
+        };
+        current = Some(letter);
+        commands.push(command);
+    }
+    Ok(commands)
+}
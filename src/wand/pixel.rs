@@ -68,7 +68,7 @@ impl PixelWand {
     }
 
     /// Set the pixel's color from hue, saturation, and lightness values.
-    pub fn set_hsl(&self, hsl: &HSL) {
+    pub fn set_hsl(&mut self, hsl: &HSL) {
         unsafe {
             bindings::PixelSetHSL(self.wand, hsl.hue, hsl.saturation, hsl.lightness);
         }
@@ -104,7 +104,7 @@ impl PixelWand {
 
     set_get_unchecked!(
         get_color_count, set_color_count, PixelGetColorCount, PixelSetColorCount,   usize
-        get_index,       set_index,       PixelGetIndex,      PixelSetIndex,        bindings::Quantum
+        get_index,       set_index,       PixelGetIndex,      PixelSetIndex,        crate::Quantum
         get_fuzz,        set_fuzz,        PixelGetFuzz,       PixelSetFuzz,         f64
     );
 
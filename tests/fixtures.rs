@@ -33,7 +33,7 @@ impl Fixture {
             .to_owned()
     }
 
-    pub fn read_image(&self, wand: &MagickWand) {
+    pub fn read_image(&self, wand: &mut MagickWand) {
         wand.read_image(&self.path_to_string())
             .unwrap_or_else(|e| panic!("Failed to read image {}: {}", self.filename, e));
     }
@@ -37,8 +37,8 @@ fn test_opening_all_fixtures_and_their_widths_and_heights() {
     });
 
     for fixture in ALL_FIXTURES.iter() {
-        let wand = MagickWand::new();
-        fixture.read_image(&wand);
+        let mut wand = MagickWand::new();
+        fixture.read_image(&mut wand);
         fixture.assert_width(&wand);
         fixture.assert_height(&wand);
     }
@@ -57,8 +57,8 @@ fn test_resize_image() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
-    IMG_5745_JPG.read_image(&wand);
+    let mut wand = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand);
     IMG_5745_JPG.assert_width(&wand);
     IMG_5745_JPG.assert_height(&wand);
 
@@ -83,8 +83,8 @@ fn test_thumbnail_image() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
-    IMG_5745_JPG.read_image(&wand);
+    let mut wand = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand);
     IMG_5745_JPG.assert_width(&wand);
     IMG_5745_JPG.assert_height(&wand);
     let halfwidth = match wand.get_image_width() {
@@ -105,7 +105,7 @@ fn test_read_from_blob() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
+    let mut wand = MagickWand::new();
 
     let mut file = IMG_5745_JPG.file();
     let mut data: Vec<u8> = Vec::new();
@@ -122,8 +122,8 @@ fn test_write_image_to_blob() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
-    IMG_5745_JPG.read_image(&wand);
+    let mut wand = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand);
     IMG_5745_JPG.assert_width(&wand);
     IMG_5745_JPG.assert_height(&wand);
     let blob = wand.write_image_blob("jpeg").unwrap();
@@ -143,8 +143,8 @@ fn test_write_images_to_blob() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
-    IMG_5745_JPG.read_image(&wand);
+    let mut wand = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand);
     IMG_5745_JPG.assert_width(&wand);
     IMG_5745_JPG.assert_height(&wand);
     let blob = wand.write_images_blob("jpeg").unwrap();
@@ -163,8 +163,8 @@ fn test_fit() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
-    IMG_5745_JPG.read_image(&wand);
+    let mut wand = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand);
     IMG_5745_JPG.assert_width(&wand);
     IMG_5745_JPG.assert_height(&wand);
     wand.fit(240, 240);
@@ -177,8 +177,8 @@ fn test_get_image_property() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
-    IMG_5745_JPG.read_image(&wand);
+    let mut wand = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand);
     // retrieve a property we know exists
     let found_value = wand.get_image_property("exif:DateTimeOriginal");
     assert!(found_value.is_ok());
@@ -197,8 +197,8 @@ fn test_requires_orientation() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
-    IMG_5745_JPG.read_image(&wand);
+    let mut wand = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand);
     assert!(!wand.requires_orientation());
 }
 
@@ -207,8 +207,8 @@ fn test_auto_orient() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
-    IMG_5745_ROTL_JPG.read_image(&wand);
+    let mut wand = MagickWand::new();
+    IMG_5745_ROTL_JPG.read_image(&mut wand);
     assert!(wand.requires_orientation());
     assert!(wand.auto_orient());
     assert!(!wand.requires_orientation());
@@ -219,11 +219,11 @@ fn test_compare_images() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand1 = MagickWand::new();
-    IMG_5745_JPG.read_image(&wand1);
+    let mut wand1 = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand1);
 
-    let wand2 = MagickWand::new();
-    IMG_5745_ROTL_JPG.read_image(&wand2);
+    let mut wand2 = MagickWand::new();
+    IMG_5745_ROTL_JPG.read_image(&mut wand2);
     wand2.auto_orient();
 
     let (distortion, diff) = wand1.compare_images(&wand2, magick_rust::MetricType::RootMeanSquared);
@@ -237,7 +237,7 @@ fn test_set_option() {
         magick_wand_genesis();
     });
     let mut wand = MagickWand::new();
-    IMG_5745_JPG.read_image(&wand);
+    IMG_5745_JPG.read_image(&mut wand);
     // The jpeg:size option is just a hint.
     wand.set_option("jpeg:size", "128x128").unwrap();
     let blob = wand.write_image_blob("jpeg").unwrap();
@@ -251,8 +251,8 @@ fn test_page_geometry() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
-    RUST_GIF.read_image(&wand);
+    let mut wand = MagickWand::new();
+    RUST_GIF.read_image(&mut wand);
     assert_eq!((156, 150, 39, 36), wand.get_image_page()); /* width, height, x offset, y offset */
     RUST_GIF.assert_width(&wand);
     RUST_GIF.assert_height(&wand);
@@ -263,8 +263,8 @@ fn test_transform_image_colorspace() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
-    IMG_5745_JPG.read_image(&wand);
+    let mut wand = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand);
     assert_eq!(
         wand.get_image_colorspace(),
         magick_rust::ColorspaceType::sRGB
@@ -298,8 +298,8 @@ fn test_color_reduction() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
-    IMG_5745_JPG.read_image(&wand);
+    let mut wand = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand);
     // There is a slight degree of variability from platform to platform,
     // and version to version of ImageMagick.
     let image_colors = wand.get_image_colors();
@@ -331,8 +331,8 @@ fn test_set_image_background_color() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
-    RUST_PNG.read_image(&wand);
+    let mut wand = MagickWand::new();
+    RUST_PNG.read_image(&mut wand);
     let mut pw = PixelWand::new();
     pw.set_color("#0000FF").unwrap();
     wand.set_image_background_color(&pw).unwrap();
@@ -379,6 +379,83 @@ fn test_get_image_channel_range() {
     assert_eq!(red.1, all.1);
 }
 
+/// A 1x1 image of the given color, for checking raw quantum values.
+fn single_pixel(color: &str) -> MagickWand {
+    let mut wand = MagickWand::new();
+    let mut pixel = PixelWand::new();
+    pixel.set_color(color).unwrap();
+    wand.new_image(1, 1, &pixel).unwrap();
+    wand
+}
+
+#[test]
+fn test_quantum_range() {
+    use magick_rust::{HDRI_ENABLED, QUANTUM_DEPTH, QUANTUM_RANGE};
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    assert_eq!(2f64.powi(QUANTUM_DEPTH as i32) - 1.0, QUANTUM_RANGE);
+    assert_eq!(cfg!(magick_hdri), HDRI_ENABLED);
+    let version = magick_rust::magick_version().unwrap();
+    assert_eq!(version.quantum_depth, QUANTUM_DEPTH);
+    assert_eq!(version.hdri, HDRI_ENABLED);
+
+    let mut pixel = PixelWand::new();
+    pixel.set_color("red").unwrap();
+    assert_eq!(QUANTUM_RANGE, pixel.get_red_quantum() as f64);
+    assert_eq!(0.0, pixel.get_green_quantum() as f64);
+    pixel.set_green_quantum(pixel.get_red_quantum());
+    assert_eq!(1.0, pixel.get_green());
+
+    // A white point of 50% maps mid gray to (at least) full intensity.
+    let mut wand = single_pixel("gray50");
+    wand.level_image(0.0, 1.0, 0.5).unwrap();
+    let (_, maxima) = wand.get_image_range().unwrap();
+    assert!((maxima - QUANTUM_RANGE).abs() / QUANTUM_RANGE < 0.01);
+}
+
+#[test]
+#[cfg(magick_hdri)]
+fn test_quantum_range_hdri() {
+    use magick_rust::{MagickEvaluateOperator, QUANTUM_RANGE};
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // HDRI pixels are floating point and are not clamped to the quantum range.
+    let mut wand = single_pixel("white");
+    wand.evaluate_image(MagickEvaluateOperator::Add, QUANTUM_RANGE)
+        .unwrap();
+    let (_, maxima) = wand.get_image_range().unwrap();
+    assert_eq!(2.0 * QUANTUM_RANGE, maxima);
+
+    let mut wand = single_pixel("white");
+    wand.evaluate_image(MagickEvaluateOperator::Subtract, 2.0 * QUANTUM_RANGE)
+        .unwrap();
+    let (minima, _) = wand.get_image_range().unwrap();
+    assert_eq!(-QUANTUM_RANGE, minima);
+}
+
+#[test]
+#[cfg(not(magick_hdri))]
+fn test_quantum_range_clamped() {
+    use magick_rust::{MagickEvaluateOperator, QUANTUM_RANGE};
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // Integer quantum values saturate at both ends of the range.
+    let mut wand = single_pixel("white");
+    wand.evaluate_image(MagickEvaluateOperator::Add, QUANTUM_RANGE)
+        .unwrap();
+    let (_, maxima) = wand.get_image_range().unwrap();
+    assert_eq!(QUANTUM_RANGE, maxima);
+
+    let mut wand = single_pixel("white");
+    wand.evaluate_image(MagickEvaluateOperator::Subtract, 2.0 * QUANTUM_RANGE)
+        .unwrap();
+    let (minima, _) = wand.get_image_range().unwrap();
+    assert_eq!(0.0, minima);
+}
+
 #[test]
 #[cfg(any(target_os = "linux", target_os = "macos"))]
 fn test_resource_limit_round_trip() {
@@ -433,181 +510,1642 @@ fn test_drawing_primitives() {
 }
 
 #[test]
-fn test_floodfill_paint_image() {
+fn test_query_font_metrics() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    // A white image with a red square in the middle. Flood-filling the white
-    // background from a corner with a transparent color is the "remove the
-    // background" operation; the red foreground must be left untouched.
-    let mut wand = MagickWand::new();
-    let mut white = PixelWand::new();
-    white.set_color("white").unwrap();
-    wand.new_image(10, 10, &white).unwrap();
-    wand.set_image_alpha_channel(magick_rust::AlphaChannelOption::Activate)
+    // No image is needed to measure text.
+    let wand = MagickWand::new();
+    let mut draw = magick_rust::DrawingWand::new();
+    draw.set_font_size(24.0);
+
+    let short = wand.query_font_metrics(&draw, "Hello").unwrap();
+    assert!(short.text_width > 0.0 && short.text_height > 0.0);
+    assert!(short.ascender > 0.0);
+    let long = wand.query_font_metrics(&draw, "Hello, world").unwrap();
+    assert!(long.text_width > short.text_width);
+
+    let two_lines = wand
+        .query_multiline_font_metrics(&draw, "Hello\nHello")
+        .unwrap();
+    assert!(two_lines.text_height > 1.5 * short.text_height);
+    assert!((two_lines.text_width - short.text_width).abs() < 1.0);
+
+    // Shrinking to fit a box smaller than the text at 24pt.
+    let size = wand
+        .fit_font_size(&mut draw, "Hello, world", long.text_width / 2.0, 100.0)
         .unwrap();
+    assert!((1.0..24.0).contains(&size));
+    assert_eq!(size, draw.get_font_size());
+    let fitted = wand.query_font_metrics(&draw, "Hello, world").unwrap();
+    assert!(fitted.text_width <= long.text_width / 2.0);
+
+    // Text that already fits keeps its size; text that never fits fails.
+    assert_eq!(
+        size,
+        wand.fit_font_size(&mut draw, "Hi", 1000.0, 1000.0).unwrap()
+    );
+    assert!(wand.fit_font_size(&mut draw, "Hello", 0.5, 0.5).is_err());
+    assert_eq!(size, draw.get_font_size());
+}
+
+#[test]
+fn test_render_caption() {
+    use magick_rust::{CaptionOptions, GravityType};
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let text = "A title of arbitrary length that has to wrap onto several lines";
+    let options = CaptionOptions {
+        width: Some(200),
+        pointsize: Some(16.0),
+        ..Default::default()
+    };
+    let caption = MagickWand::render_caption(text, &options).unwrap();
+    assert_eq!(200, caption.get_image_width());
+    let label = MagickWand::render_label(text, &options).unwrap();
+    // The caption wraps, so it is taller than the single line of the label.
+    assert!(caption.get_image_height() > label.get_image_height());
+
+    // With only the box given, the text is sized to fill it exactly.
+    let mut fill = PixelWand::new();
+    fill.set_color("white").unwrap();
+    let mut background = PixelWand::new();
+    background.set_color("navy").unwrap();
+    let card = MagickWand::render_caption(
+        text,
+        &CaptionOptions {
+            width: Some(300),
+            height: Some(150),
+            gravity: Some(GravityType::Center),
+            fill: Some(fill),
+            background: Some(background),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(300, card.get_image_width());
+    assert_eq!(150, card.get_image_height());
+    let corner = card.get_image_pixel_color(0, 0).unwrap();
+    assert!(corner.get_blue() > 0.4 && corner.get_red() < 0.1);
+
+    // Text is taken literally rather than as escapes or a file to read.
+    let options = CaptionOptions {
+        pointsize: Some(16.0),
+        ..Default::default()
+    };
+    let literal = MagickWand::render_label("@/etc/passwd 100% [1]", &options).unwrap();
+    let plain = MagickWand::render_label("x", &options).unwrap();
+    assert!(literal.get_image_width() > 5 * plain.get_image_width());
+    // A trailing `[...]` is drawn as is, neither dropped nor padded.
+    let mut drawing = magick_rust::DrawingWand::new();
+    drawing.set_font_size(16.0);
+    for text in ["Item [1]", "Item"] {
+        let label = MagickWand::render_label(text, &options).unwrap();
+        let metrics = label.query_font_metrics(&drawing, text).unwrap();
+        let width = label.get_image_width() as f64;
+        assert!((width - metrics.text_width).abs() <= 2.0, "{text}: {width}");
+    }
+}
 
+#[test]
+fn test_path_builder() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut white = PixelWand::new();
+    white.set_color("white").unwrap();
     let mut red = PixelWand::new();
     red.set_color("red").unwrap();
+    let is_red = |wand: &MagickWand, x: isize, y: isize| {
+        let pixel = wand.get_image_pixel_color(x, y).unwrap();
+        pixel.get_red() > 0.5 && pixel.get_green() < 0.5
+    };
+
+    // A square in absolute coordinates and a triangle in relative ones.
+    let mut wand = MagickWand::new();
+    wand.new_image(100, 100, &white).unwrap();
     let mut draw = magick_rust::DrawingWand::new();
     draw.set_fill_color(&red);
-    draw.draw_rectangle(3.0, 3.0, 6.0, 6.0);
+    draw.path()
+        .move_to(10.0, 10.0)
+        .horizontal_line_to(40.0)
+        .vertical_line_to(40.0)
+        .line_to(10.0, 40.0)
+        .close();
+    let mut path = draw.path();
+    path.move_to(60.0, 90.0)
+        .line_to_relative(30.0, 0.0)
+        .line_to_relative(-15.0, -30.0)
+        .close();
+    path.finish();
     wand.draw_image(&draw).unwrap();
+    assert!(is_red(&wand, 25, 25));
+    assert!(is_red(&wand, 75, 85));
+    assert!(!is_red(&wand, 50, 50));
+    assert!(!is_red(&wand, 62, 62));
 
-    let mut transparent = PixelWand::new();
-    transparent.set_color("none").unwrap();
-    wand.floodfill_paint_image(&transparent, 0.0, &white, 0, 0, false)
+    // The same shapes from SVG path data, plus a circle made of two arcs.
+    let mut wand = MagickWand::new();
+    wand.new_image(100, 100, &white).unwrap();
+    let mut draw = magick_rust::DrawingWand::new();
+    draw.set_fill_color(&red);
+    draw.draw_svg_path("M10,10 H40 V40 L10 40 z M60 90 l30 0 -15-30 Z")
         .unwrap();
+    draw.draw_svg_path("M50 50 a10 10 0 1 0 0.1 0 z").unwrap();
+    wand.draw_image(&draw).unwrap();
+    assert!(is_red(&wand, 25, 25));
+    assert!(is_red(&wand, 75, 85));
+    assert!(is_red(&wand, 50, 60));
+    assert!(!is_red(&wand, 50, 80));
 
-    // The flooded corner is now transparent...
-    let corner = wand.get_image_pixel_color(0, 0).unwrap();
-    assert_eq!(0.0, corner.get_alpha());
-    // ...while the red foreground square remains fully opaque.
-    let center = wand.get_image_pixel_color(4, 4).unwrap();
-    assert_eq!(1.0, center.get_alpha());
-    assert!(center.get_red() > 0.5 && center.get_green() < 0.5 && center.get_blue() < 0.5);
+    let mut draw = magick_rust::DrawingWand::new();
+    assert!(draw.draw_svg_path("M10 10 L20").is_err());
+    assert!(draw.draw_svg_path("10 10").is_err());
+    assert!(draw.draw_svg_path("M10 10 X20 20").is_err());
+    assert!(draw.draw_svg_path("M1e2.5 c.5.5 1 1 2 2").is_ok());
 }
 
 #[test]
-fn test_transparent_paint_image() {
+fn test_affine_matrix() {
+    use magick_rust::AffineMatrix;
+    let close = |(ax, ay): (f64, f64), (bx, by): (f64, f64)| {
+        (ax - bx).abs() < 1e-9 && (ay - by).abs() < 1e-9
+    };
+    assert!(close(
+        (0.0, 1.0),
+        AffineMatrix::rotation(90.0).transform_point((1.0, 0.0))
+    ));
+    let matrix = AffineMatrix::scaling(2.0, 3.0).then(&AffineMatrix::translation(10.0, 20.0));
+    assert!(close((12.0, 23.0), matrix.transform_point((1.0, 1.0))));
+    let matrix = AffineMatrix::translation(10.0, 20.0).then(&AffineMatrix::scaling(2.0, 3.0));
+    assert!(close((22.0, 63.0), matrix.transform_point((1.0, 1.0))));
+    assert!(close(
+        (2.0, 1.0),
+        AffineMatrix::skew_x(45.0).transform_point((1.0, 1.0))
+    ));
+    assert_eq!(AffineMatrix::IDENTITY, AffineMatrix::default());
+}
+
+#[test]
+fn test_drawing_graphic_context() {
+    use magick_rust::AffineMatrix;
     START.call_once(|| {
         magick_wand_genesis();
     });
-    // A white image with a red square in the middle. Painting white as
-    // transparent is the "-transparent white" operation; unlike a floodfill it
-    // is not connectivity-bound, so every white pixel disappears while the red
-    // foreground stays opaque.
-    let mut wand = MagickWand::new();
     let mut white = PixelWand::new();
     white.set_color("white").unwrap();
-    wand.new_image(10, 10, &white).unwrap();
-    wand.set_image_alpha_channel(magick_rust::AlphaChannelOption::Activate)
-        .unwrap();
-
     let mut red = PixelWand::new();
     red.set_color("red").unwrap();
+    let mut blue = PixelWand::new();
+    blue.set_color("blue").unwrap();
+    let mut wand = MagickWand::new();
+    wand.new_image(100, 100, &white).unwrap();
+
     let mut draw = magick_rust::DrawingWand::new();
-    draw.set_fill_color(&red);
-    draw.draw_rectangle(3.0, 3.0, 6.0, 6.0);
+    draw.set_fill_color(&blue);
+    {
+        // A bar rotated by 90 degrees around the center of the image.
+        let mut context = draw.push_context().unwrap();
+        context.set_fill_color(&red);
+        context.translate(50.0, 50.0);
+        context.rotate(90.0);
+        context.draw_rectangle(-40.0, -5.0, 40.0, 5.0);
+    }
+    // Outside the context the fill color and coordinates are restored.
+    draw.draw_rectangle(0.0, 0.0, 10.0, 10.0);
+    draw.push_graphic_context().unwrap();
+    draw.affine(&AffineMatrix::translation(80.0, 80.0));
+    draw.draw_rectangle(0.0, 0.0, 10.0, 10.0);
+    draw.pop_graphic_context().unwrap();
     wand.draw_image(&draw).unwrap();
 
-    // alpha == 0.0 makes the matched (white) pixels fully transparent.
-    wand.transparent_paint_image(&white, 0.0, 0.0, false)
-        .unwrap();
+    let color = |x, y| {
+        let pixel = wand.get_image_pixel_color(x, y).unwrap();
+        (
+            pixel.get_red() > 0.5,
+            pixel.get_green() > 0.5,
+            pixel.get_blue() > 0.5,
+        )
+    };
+    assert_eq!((true, false, false), color(50, 80));
+    assert_eq!((true, true, true), color(80, 50));
+    assert_eq!((false, false, true), color(5, 5));
+    assert_eq!((false, false, true), color(85, 85));
+}
 
-    // Every white pixel is now transparent. The alpha comes back from
-    // ImageMagick's quantum->normalized-float conversion, so it may be a tiny
-    // denormal rather than exactly 0.0; compare within a tolerance.
-    let corner = wand.get_image_pixel_color(0, 0).unwrap();
-    assert!(corner.get_alpha() < 1e-6);
-    // ...while the red foreground square remains fully opaque.
-    let center = wand.get_image_pixel_color(4, 4).unwrap();
-    assert!(center.get_alpha() > 1.0 - 1e-6);
-    assert!(center.get_red() > 0.5 && center.get_green() < 0.5 && center.get_blue() < 0.5);
+#[test]
+fn test_drawing_patterns_and_gradients() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let color = |name: &str| {
+        let mut pixel = PixelWand::new();
+        pixel.set_color(name).unwrap();
+        pixel
+    };
+    let mut wand = MagickWand::new();
+    wand.new_image(200, 100, &color("white")).unwrap();
+
+    // A 20x20 checkerboard tile filling the left half.
+    let mut draw = magick_rust::DrawingWand::new();
+    let red = color("red");
+    draw.define_pattern("checker", (0.0, 0.0, 20.0, 20.0), |pattern| {
+        pattern.set_fill_color(&red);
+        pattern.draw_rectangle(0.0, 0.0, 9.0, 9.0);
+        pattern.draw_rectangle(10.0, 10.0, 19.0, 19.0);
+        Ok(())
+    })
+    .unwrap();
+    draw.set_fill_pattern("checker").unwrap();
+    draw.draw_rectangle(0.0, 0.0, 99.0, 99.0);
+
+    // A black to white gradient running left to right over the right half.
+    draw.define_linear_gradient(
+        "fade",
+        (100.0, 0.0, 100.0, 100.0),
+        &color("black"),
+        &color("white"),
+        90.0,
+    )
+    .unwrap();
+    draw.set_fill_pattern("#fade").unwrap();
+    draw.draw_rectangle(100.0, 0.0, 199.0, 99.0);
+    wand.draw_image(&draw).unwrap();
+
+    let is_red = |x, y| {
+        let pixel = wand.get_image_pixel_color(x, y).unwrap();
+        pixel.get_red() > 0.5 && pixel.get_green() < 0.5
+    };
+    assert!(is_red(5, 5));
+    assert!(!is_red(15, 5));
+    assert!(is_red(35, 55));
+    assert!(!is_red(45, 55));
+    let left = wand.get_image_pixel_color(110, 50).unwrap().get_green();
+    let right = wand.get_image_pixel_color(190, 50).unwrap().get_green();
+    assert!(left < 0.3 && right > 0.7, "gradient {left} -> {right}");
+
+    // Radial gradients and stroke patterns; unknown patterns are an error.
+    let mut draw = magick_rust::DrawingWand::new();
+    draw.define_radial_gradient(
+        "glow",
+        (0.0, 0.0, 50.0, 50.0),
+        &color("yellow"),
+        &color("blue"),
+    )
+    .unwrap();
+    draw.set_stroke_pattern("glow").unwrap();
+    assert!(draw.set_fill_pattern("missing").is_err());
 }
 
 #[test]
-fn test_set_background_color() {
+fn test_drawing_dashes_and_composite() {
+    use magick_rust::CompositeOperator;
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
-    let mut pw = PixelWand::new();
-    pw.set_color("none").unwrap();
-    wand.set_background_color(&pw).unwrap();
-    RUST_SVG.read_image(&wand);
-    let blob = wand.write_image_blob("rgba").unwrap();
-    assert_eq!(0u8, blob[0]);
-    assert_eq!(0u8, blob[1]);
-    assert_eq!(0u8, blob[2]);
-    assert_eq!(0u8, blob[3]);
+    let color = |name: &str| {
+        let mut pixel = PixelWand::new();
+        pixel.set_color(name).unwrap();
+        pixel
+    };
+    let mut wand = MagickWand::new();
+    wand.new_image(100, 100, &color("white")).unwrap();
+    let mut logo = MagickWand::new();
+    logo.new_image(10, 10, &color("blue")).unwrap();
+
+    let mut draw = magick_rust::DrawingWand::new();
+    assert!(draw.get_stroke_dash_array().is_empty());
+    draw.set_stroke_dash_array(&[10.0, 10.0]).unwrap();
+    assert_eq!(vec![10.0, 10.0], draw.get_stroke_dash_array());
+    draw.set_stroke_color(&color("red"));
+    draw.set_stroke_width(3.0);
+    draw.draw_line(0.0, 10.5, 100.0, 10.5);
+    draw.set_stroke_dash_array(&[]).unwrap();
+    assert!(draw.get_stroke_dash_array().is_empty());
+    // The logo, scaled up, in the same drawing.
+    draw.draw_composite(CompositeOperator::Over, 50.0, 50.0, 40.0, 40.0, &logo)
+        .unwrap();
+    drop(logo);
+    wand.draw_image(&draw).unwrap();
+
+    let pixel = |x, y| {
+        let pixel = wand.get_image_pixel_color(x, y).unwrap();
+        (pixel.get_red() > 0.5, pixel.get_blue() > 0.5)
+    };
+    assert_eq!((true, false), pixel(5, 10));
+    assert_eq!((true, true), pixel(15, 10));
+    assert_eq!((false, true), pixel(85, 85));
+    assert_eq!((true, true), pixel(45, 45));
+
+    let mut draw = magick_rust::DrawingWand::new();
+    draw.set_font_resolution(144.0, 144.0).unwrap();
+    assert_eq!((144.0, 144.0), draw.get_font_resolution().unwrap());
+    draw.set_density("300x300").unwrap();
+    assert_eq!("300x300", draw.get_density().unwrap());
 }
 
 #[test]
-fn test_set_size() {
-    let wand = MagickWand::new();
-    assert!(wand.set_size(100, 100).is_ok());
+#[cfg(magick_7_1_1_26)]
+fn test_drawing_type_metrics() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut draw = magick_rust::DrawingWand::new();
+    draw.set_font_size(24.0);
+    let metrics = draw.get_type_metrics("Hello", true).unwrap();
+    let queried = MagickWand::new()
+        .query_font_metrics(&draw, "Hello")
+        .unwrap();
+    assert!(metrics.text_width > 0.0);
+    assert!((metrics.text_width - queried.text_width).abs() < 1.0);
+    let two_lines = draw.get_type_metrics("Hello\nHello", false).unwrap();
+    assert!(two_lines.text_height > metrics.text_height);
 }
 
 #[test]
-fn test_clut_image() {
+fn test_drawing_mvg_and_svg() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
-    IMG_5745_JPG.read_image(&wand);
+    let mut draw = magick_rust::DrawingWand::new();
+    draw.load_mvg(
+        "# background and a badge
+        push graphic-context
+          fill blue
+          rectangle 0,0 49,99
+        pop graphic-context
+        fill 'rgb(255, 0, 0)' stroke none
+        translate 50,0
+        circle 25,50 25,30
+        text 0,90 'Tom & \\'Jerry\\''",
+    )
+    .unwrap();
+    assert!(draw.load_mvg("rectangle 0,0 10").is_err());
+    assert!(draw.load_mvg("fill red\nwobble 1,2").is_err());
 
-    let mut gradient = MagickWand::new();
-    assert!(gradient.set_size(128, 20).is_ok());
-    assert!(gradient.set_option("gradient:angle", "90").is_ok());
-    assert!(gradient.read_image("gradient:black-yellow").is_ok());
+    let mut white = PixelWand::new();
+    white.set_color("white").unwrap();
+    let mut wand = MagickWand::new();
+    wand.new_image(100, 100, &white).unwrap();
+    wand.draw_image(&draw).unwrap();
+    let pixel = |x, y| {
+        let pixel = wand.get_image_pixel_color(x, y).unwrap();
+        (pixel.get_red() > 0.5, pixel.get_blue() > 0.5)
+    };
+    assert_eq!((false, true), pixel(10, 10));
+    assert_eq!((true, false), pixel(75, 50));
+    assert_eq!((true, true), pixel(75, 10));
+
+    // The recorded script loads into another wand and draws the same.
+    let mvg = draw.get_mvg().unwrap();
+    assert!(mvg.contains("circle"));
+    let mut copy = magick_rust::DrawingWand::new();
+    copy.load_mvg(&mvg).unwrap();
+    let mut other = MagickWand::new();
+    other.new_image(100, 100, &white).unwrap();
+    other.draw_image(&copy).unwrap();
+    assert_eq!(
+        wand.export_image_pixels(0, 0, 100, 100, "RGB"),
+        other.export_image_pixels(0, 0, 100, 100, "RGB")
+    );
 
+    let svg = draw.to_svg().unwrap();
+    assert!(svg.starts_with("<?xml"));
     assert!(
-        wand.clut_image(&gradient, magick_rust::PixelInterpolateMethod::Bilinear)
-            .is_ok()
+        svg.contains("<rect x=\"0\" y=\"0\" width=\"49\" height=\"99\" fill=\"rgb(0,0,255)\"/>")
     );
+    assert!(svg.contains("<circle cx=\"25\" cy=\"50\" r=\"20\" fill=\"rgb(255,0,0)\" transform=\"matrix(1 0 0 1 50 0)\"/>"));
+    assert!(svg.contains(">Tom &amp; &apos;Jerry&apos;</text>"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+
+    // Embedded images use xlink:href, which SVG 1.1 renderers understand.
+    let mut badge = MagickWand::new();
+    RUST_PNG.read_image(&mut badge);
+    let mut draw = magick_rust::DrawingWand::new();
+    draw.draw_composite(
+        magick_rust::CompositeOperator::Over,
+        10.0,
+        10.0,
+        4.0,
+        4.0,
+        &badge,
+    )
+    .unwrap();
+    let svg = draw.to_svg().unwrap();
+    assert!(svg.contains("xmlns:xlink=\"http://www.w3.org/1999/xlink\""));
+    assert!(svg.contains("xlink:href=\"data:image/png;base64,"));
 }
 
 #[test]
-fn test_negate_image() {
+fn test_drawing_clip_path_and_masks() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
-    RUST_PNG.read_image(&wand);
+    let color = |name: &str| {
+        let mut pixel = PixelWand::new();
+        pixel.set_color(name).unwrap();
+        pixel
+    };
+    let mut draw = magick_rust::DrawingWand::new();
+    draw.define_clip_path("window", |clip| {
+        clip.draw_circle(50.0, 50.0, 50.0, 30.0);
+        Ok(())
+    })
+    .unwrap();
+    draw.set_clip_path("window").unwrap();
+    draw.set_fill_color(&color("red"));
+    draw.draw_rectangle(0.0, 0.0, 99.0, 99.0);
+    let mut wand = MagickWand::new();
+    wand.new_image(100, 100, &color("white")).unwrap();
+    wand.draw_image(&draw).unwrap();
+    let is_red = |wand: &MagickWand, x, y| {
+        let pixel = wand.get_image_pixel_color(x, y).unwrap();
+        pixel.get_red() > 0.5 && pixel.get_green() < 0.5
+    };
+    assert!(is_red(&wand, 50, 50));
+    assert!(!is_red(&wand, 5, 5));
+    let svg = draw.to_svg().unwrap();
+    assert!(svg.contains("<clipPath id=\"window\""));
+    assert!(svg.contains("clip-path=\"url(#window)\""));
+
+    // A write mask limits the negation to the left half.
+    let mut left = magick_rust::DrawingWand::new();
+    left.draw_rectangle(0.0, 0.0, 49.0, 99.0);
+    let mut wand = MagickWand::new();
+    wand.new_image(100, 100, &color("white")).unwrap();
+    wand.with_drawing_mask(&left, |wand| wand.negate_image())
+        .unwrap();
+    let brightness = |wand: &MagickWand, x, y| wand.get_image_pixel_color(x, y).unwrap().get_red();
+    assert!(brightness(&wand, 10, 50) < 0.5);
+    assert!(brightness(&wand, 90, 50) > 0.5);
+    // The mask is gone afterwards.
     wand.negate_image().unwrap();
-    let pixel_color = wand.get_image_pixel_color(0, 0).unwrap();
-    assert_eq!(
-        "srgb(255,255,255)",
-        pixel_color.get_color_as_string().unwrap()
-    );
+    assert!(brightness(&wand, 10, 50) > 0.5);
+    assert!(brightness(&wand, 90, 50) < 0.5);
 }
 
 #[test]
-// opt-in platforms that have resource limits support
-#[cfg(any(target_os = "linux", target_os = "macos"))]
-fn test_resource_limits() {
-    use magick_rust::ResourceType;
+fn test_charcoal_image() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    MagickWand::set_resource_limit(ResourceType::Thread, 1).unwrap();
-    let wand = MagickWand::new();
-    RUST_PNG.read_image(&wand);
+    let mut wand = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand);
+    wand.charcoal_image(0.0, 1.0).unwrap();
+    IMG_5745_JPG.assert_width(&wand);
+    IMG_5745_JPG.assert_height(&wand);
+    // A charcoal drawing has no color left.
+    let pixels = wand.export_image_pixels(0, 0, 64, 64, "RGB").unwrap();
+    assert!(
+        pixels
+            .chunks(3)
+            .all(|rgb| rgb[0] == rgb[1] && rgb[1] == rgb[2])
+    );
 }
 
 #[test]
-fn test_auto_level() {
+fn test_sketch_image() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
-    IMG_5745_JPG.read_image(&wand);
-    assert!(wand.auto_level().is_ok());
+    let mut original = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut original);
+    let mut wand = original.clone();
+    wand.sketch_image(0.0, 5.0, 45.0).unwrap();
+    IMG_5745_JPG.assert_width(&wand);
+    IMG_5745_JPG.assert_height(&wand);
+    // The pencil strokes are dodged onto the image, which only lightens it.
+    assert!(wand.get_image_mean().unwrap().0 > original.get_image_mean().unwrap().0);
 }
 
 #[test]
-fn test_auto_gamma() {
+fn test_oil_paint_image() {
     START.call_once(|| {
         magick_wand_genesis();
     });
-    let wand = MagickWand::new();
-    IMG_5745_JPG.read_image(&wand);
-    assert!(wand.auto_gamma().is_ok());
+    let mut original = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut original);
+    let mut wand = original.clone();
+    wand.oil_paint_image(3.0, 1.0).unwrap();
+    IMG_5745_JPG.assert_width(&wand);
+    IMG_5745_JPG.assert_height(&wand);
+    // Every pixel takes a color from its neighborhood, so colors only merge.
+    assert!(wand.get_image_colors() < original.get_image_colors());
 }
 
 #[test]
-fn test_image_compose() {
+fn test_swirl_image() {
+    use magick_rust::PixelInterpolateMethod;
     START.call_once(|| {
         magick_wand_genesis();
     });
+    let mut original = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut original);
+    let mut wand = original.clone();
+    wand.swirl_image(180.0, PixelInterpolateMethod::Average)
+        .unwrap();
+    IMG_5745_JPG.assert_width(&wand);
+    IMG_5745_JPG.assert_height(&wand);
+    // The corners lie outside the swirl and are left as they were, while the
+    // area around the center is turned.
+    let corner = |wand: &MagickWand| wand.export_image_pixels(0, 0, 16, 16, "RGB").unwrap();
+    assert_eq!(corner(&original), corner(&wand));
+    let center = |wand: &MagickWand| wand.export_image_pixels(192, 128, 128, 128, "RGB").unwrap();
+    assert_ne!(center(&original), center(&wand));
+
+    // The effect also applies frame by frame.
     let mut wand = MagickWand::new();
-    wand.new_image(4, 4, &PixelWand::new()).unwrap();
+    RUST_GIF.read_image(&mut wand);
+    wand.images_mut()
+        .try_for_each(|_, mut frame| frame.swirl_image(90.0, PixelInterpolateMethod::Average))
+        .unwrap();
+}
 
-    let operators = [
-        magick_rust::CompositeOperator::Alpha,
-        magick_rust::CompositeOperator::MinusDst,
-        magick_rust::CompositeOperator::Over,
+#[test]
+fn test_wave_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut wand = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand);
+    wand.wave_image(10.0, 100.0, magick_rust::PixelInterpolateMethod::Average)
+        .unwrap();
+    IMG_5745_JPG.assert_width(&wand);
+    assert_eq!(384 + 20, wand.get_image_height());
+}
+
+#[test]
+fn test_polaroid_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut wand = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand);
+    let caption = magick_rust::DrawingWand::new();
+    wand.polaroid_image(
+        &caption,
+        "Cat",
+        5.0,
+        magick_rust::PixelInterpolateMethod::Average,
+    )
+    .unwrap();
+    assert!(wand.get_image_width() > 512);
+    assert!(wand.get_image_height() > 384);
+}
+
+#[test]
+fn test_vignette_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut original = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut original);
+    let mut black = PixelWand::new();
+    black.set_color("black").unwrap();
+    original.set_image_background_color(&black).unwrap();
+    let mut wand = original.clone();
+    wand.vignette_image(0.0, 10.0, 20, 20).unwrap();
+    IMG_5745_JPG.assert_width(&wand);
+    IMG_5745_JPG.assert_height(&wand);
+    // The corners fade into the background color, the center is untouched.
+    let corner = wand.get_image_pixel_color(0, 0).unwrap();
+    assert!(corner.get_red() < 0.05 && corner.get_green() < 0.05 && corner.get_blue() < 0.05);
+    let center = |wand: &MagickWand| wand.get_image_pixel_color(256, 192).unwrap().get_red();
+    assert!((center(&wand) - center(&original)).abs() < 0.02);
+}
+
+#[test]
+fn test_emboss_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // Without edges there is no relief.
+    let mut gray = PixelWand::new();
+    gray.set_color("gray50").unwrap();
+    let mut wand = MagickWand::new();
+    wand.new_image(32, 32, &gray).unwrap();
+    wand.emboss_image(0.0, 1.0).unwrap();
+    assert_eq!(1, wand.get_image_colors());
+
+    let mut wand = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand);
+    wand.emboss_image(0.0, 1.0).unwrap();
+    IMG_5745_JPG.assert_width(&wand);
+    IMG_5745_JPG.assert_height(&wand);
+    assert!(wand.get_image_colors() > 1);
+}
+
+#[test]
+fn test_shade_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut red = PixelWand::new();
+    red.set_color("red").unwrap();
+    let mut flat = MagickWand::new();
+    flat.new_image(32, 32, &red).unwrap();
+    // A flat surface lit from 30 degrees above reflects sin(30) = 0.5 of the
+    // light, which replaces the colors with gray.
+    let mut wand = flat.clone();
+    wand.shade_image(true, 30.0, 30.0).unwrap();
+    let pixel = wand.get_image_pixel_color(16, 16).unwrap();
+    assert!((pixel.get_red() - 0.5).abs() < 0.01);
+    assert!((pixel.get_green() - 0.5).abs() < 0.01);
+    assert!((pixel.get_blue() - 0.5).abs() < 0.01);
+    // Without `gray` the shading scales the original colors instead.
+    let mut wand = flat.clone();
+    wand.shade_image(false, 30.0, 30.0).unwrap();
+    let pixel = wand.get_image_pixel_color(16, 16).unwrap();
+    assert!((pixel.get_red() - 0.5).abs() < 0.01);
+    assert!(pixel.get_green() < 0.01);
+    assert!(pixel.get_blue() < 0.01);
+}
+
+#[test]
+fn test_solarize_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut gradient = MagickWand::new();
+    gradient.set_size(64, 64).unwrap();
+    gradient.read_image("gradient:black-white").unwrap();
+    let mut wand = gradient.clone();
+    wand.solarize_image(0.5).unwrap();
+    let value = |wand: &MagickWand, y| wand.get_image_pixel_color(0, y).unwrap().get_red();
+    // Values below the threshold are kept, values above it are inverted.
+    assert_eq!(value(&gradient, 16), value(&wand, 16));
+    assert!((value(&wand, 48) - (1.0 - value(&gradient, 48))).abs() < 0.01);
+    assert!(value(&wand, 63) < 0.01);
+}
+
+/// A 64x64 black image with a white dot at (x, y).
+fn dot_image(x: isize, y: isize) -> MagickWand {
+    let mut black = PixelWand::new();
+    black.set_color("black").unwrap();
+    let mut wand = MagickWand::new();
+    wand.new_image(64, 64, &black).unwrap();
+    wand.import_image_pixels(x, y, 1, 1, &[255, 255, 255], "RGB")
+        .unwrap();
+    wand
+}
+
+/// A 64x64 gray image whose left half has the value `left` and right half the
+/// value `right`, so there is a vertical edge between columns 31 and 32.
+fn halves_image(left: u8, right: u8) -> MagickWand {
+    let mut black = PixelWand::new();
+    black.set_color("black").unwrap();
+    let mut wand = MagickWand::new();
+    wand.new_image(64, 64, &black).unwrap();
+    wand.import_image_pixels(0, 0, 32, 64, &[left; 32 * 64 * 3], "RGB")
+        .unwrap();
+    wand.import_image_pixels(32, 0, 32, 64, &[right; 32 * 64 * 3], "RGB")
+        .unwrap();
+    wand
+}
+
+/// The gray level of the pixel at (x, y), from 0 to 1.
+fn gray_at(wand: &MagickWand, x: isize, y: isize) -> f64 {
+    wand.get_image_pixel_color(x, y).unwrap().get_red()
+}
+
+#[test]
+fn test_motion_blur_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // At 0 degrees the dot is smeared along its row only.
+    let mut wand = dot_image(32, 32);
+    wand.motion_blur_image(0.0, 2.0, 0.0).unwrap();
+    assert!(gray_at(&wand, 30, 32) > 0.0 || gray_at(&wand, 34, 32) > 0.0);
+    assert_eq!(0.0, gray_at(&wand, 32, 30));
+    assert_eq!(0.0, gray_at(&wand, 32, 34));
+    // At 90 degrees along its column only.
+    let mut wand = dot_image(32, 32);
+    wand.motion_blur_image(0.0, 2.0, 90.0).unwrap();
+    assert!(gray_at(&wand, 32, 30) > 0.0 || gray_at(&wand, 32, 34) > 0.0);
+    assert_eq!(0.0, gray_at(&wand, 30, 32));
+    assert_eq!(0.0, gray_at(&wand, 34, 32));
+}
+
+#[test]
+fn test_rotational_blur_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // The dot is smeared along the circle around the center of the image
+    // that it lies on, not towards or away from the center.
+    let mut wand = dot_image(56, 32);
+    wand.rotational_blur_image(30.0).unwrap();
+    let pixels = wand.export_image_pixels(0, 0, 64, 64, "R").unwrap();
+    let mut lit = 0;
+    for (index, &value) in pixels.iter().enumerate() {
+        if value > 0 {
+            let (x, y) = ((index % 64) as f64, (index / 64) as f64);
+            let distance = (x - 31.5).hypot(y - 31.5);
+            assert!((distance - 24.5).abs() < 2.0, "({x}, {y}) is lit");
+            lit += 1;
+        }
+    }
+    assert!(lit > 1);
+}
+
+#[test]
+fn test_selective_blur_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // A strong edge is left alone, which a plain blur would soften...
+    let mut wand = halves_image(0, 255);
+    wand.selective_blur_image(0.0, 3.0, 0.2).unwrap();
+    assert!(gray_at(&wand, 31, 32) < 0.01);
+    assert!(gray_at(&wand, 32, 32) > 0.99);
+    let mut blurred = halves_image(0, 255);
+    blurred.blur_image(0.0, 3.0).unwrap();
+    assert!(gray_at(&blurred, 31, 32) > 0.1);
+    // ...but an edge with less contrast than the threshold is blurred.
+    let mut wand = halves_image(115, 140);
+    wand.selective_blur_image(0.0, 3.0, 0.2).unwrap();
+    assert!(gray_at(&wand, 31, 32) > 115.0 / 255.0 + 0.01);
+}
+
+#[test]
+fn test_adaptive_blur_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // The blur is reduced near edges, so the edge stays sharper than with a
+    // plain blur of the same strength.
+    let contrast = |wand: &MagickWand| gray_at(wand, 33, 32) - gray_at(wand, 30, 32);
+    let mut adaptive = halves_image(0, 255);
+    adaptive.adaptive_blur_image(0.0, 3.0).unwrap();
+    let mut blurred = halves_image(0, 255);
+    blurred.blur_image(0.0, 3.0).unwrap();
+    assert!(contrast(&adaptive) > contrast(&blurred));
+}
+
+#[test]
+fn test_adaptive_sharpen_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut soft = halves_image(64, 191);
+    soft.blur_image(0.0, 2.0).unwrap();
+    let contrast = |wand: &MagickWand| gray_at(wand, 34, 32) - gray_at(wand, 29, 32);
+    let mut wand = soft.clone();
+    wand.adaptive_sharpen_image(0.0, 3.0).unwrap();
+    assert!(contrast(&wand) > contrast(&soft));
+}
+
+#[test]
+fn test_unsharp_mask_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut soft = halves_image(64, 191);
+    soft.blur_image(0.0, 2.0).unwrap();
+    let mut wand = soft.clone();
+    wand.unsharp_mask_image(0.0, 2.0, 2.0, 0.0).unwrap();
+    // The dark side of the edge gets darker and the light side lighter.
+    assert!(gray_at(&wand, 28, 32) < gray_at(&soft, 28, 32));
+    assert!(gray_at(&wand, 35, 32) > gray_at(&soft, 35, 32));
+}
+
+#[test]
+#[cfg(magick_7_0_8_0)]
+fn test_kuwahara_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // Each side of the edge is smoothed from its own side only, so the edge
+    // stays sharper than with a plain Gaussian blur.
+    let mut wand = halves_image(0, 255);
+    wand.kuwahara_image(3.0, 1.5).unwrap();
+    let mut blurred = halves_image(0, 255);
+    blurred.blur_image(3.0, 1.5).unwrap();
+    assert!(gray_at(&wand, 31, 32) < gray_at(&blurred, 31, 32));
+    assert!(gray_at(&wand, 32, 32) > gray_at(&blurred, 32, 32));
+}
+
+#[test]
+fn test_noise_handling() {
+    use magick_rust::{NoiseType, QUANTUM_RANGE};
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut gray = PixelWand::new();
+    gray.set_color("gray50").unwrap();
+    let mut clean = MagickWand::new();
+    clean.new_image(128, 128, &gray).unwrap();
+    let (mean, standard_deviation) = clean.get_image_mean().unwrap();
+    assert!((mean / QUANTUM_RANGE - 0.5).abs() < 0.01);
+    assert!(standard_deviation / QUANTUM_RANGE < 0.001);
+    assert!(clean.estimate_noise().unwrap() < 0.001);
+
+    let mut noisy = clean.clone();
+    noisy.add_noise_image(NoiseType::Gaussian, 5.0).unwrap();
+    let noise = noisy.estimate_noise().unwrap();
+    assert!(noise > 0.001, "noise estimate {noise}");
+
+    let mut despeckled = noisy.clone();
+    despeckled.despeckle_image().unwrap();
+    assert!(despeckled.estimate_noise().unwrap() < noise);
+    let mut enhanced = noisy.clone();
+    enhanced.enhance_image().unwrap();
+    assert!(enhanced.estimate_noise().unwrap() < noise);
+
+    #[cfg(magick_7_0_8_0)]
+    {
+        let mut denoised = noisy.clone();
+        let threshold = denoised.auto_wavelet_denoise_image(0.0).unwrap();
+        assert!(threshold > 0.0);
+        assert!(denoised.estimate_noise().unwrap() < noise);
+        let mut segmented = noisy.clone();
+        segmented.mean_shift_image(7, 7, 0.1).unwrap();
+        assert!(segmented.estimate_noise().unwrap() < noise);
+    }
+}
+
+/// A 128x128 vertical gradient from gray40 at the top to gray60 at the bottom.
+fn low_contrast_gradient() -> MagickWand {
+    let mut wand = MagickWand::new();
+    wand.set_size(128, 128).unwrap();
+    wand.read_image("gradient:gray40-gray60").unwrap();
+    wand
+}
+
+#[test]
+fn test_equalize_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // Every row of the gradient holds the same number of pixels, so a flat
+    // histogram spreads the rows evenly over the whole range.
+    let mut wand = low_contrast_gradient();
+    wand.equalize_image().unwrap();
+    assert!((gray_at(&wand, 0, 32) - 0.25).abs() < 0.02);
+    assert!((gray_at(&wand, 0, 96) - 0.75).abs() < 0.02);
+    assert!(gray_at(&wand, 0, 127) > 0.99);
+}
+
+#[test]
+fn test_linear_stretch_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // Each row has 128 pixels, so the darkest and lightest rows hold the 100
+    // pixels to turn black and white, and the rest is stretched in between.
+    let mut wand = low_contrast_gradient();
+    wand.linear_stretch_image(100.0, 100.0).unwrap();
+    assert!(gray_at(&wand, 0, 0) < 0.01);
+    assert!((gray_at(&wand, 0, 64) - 0.5).abs() < 0.02);
+    assert!(gray_at(&wand, 0, 127) > 0.99);
+}
+
+#[test]
+fn test_contrast_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let gradient = low_contrast_gradient();
+    let mut wand = gradient.clone();
+    wand.contrast_image(true).unwrap();
+    assert!(gray_at(&wand, 0, 0) < gray_at(&gradient, 0, 0));
+    assert!(gray_at(&wand, 0, 127) > gray_at(&gradient, 0, 127));
+    let mut wand = gradient.clone();
+    wand.contrast_image(false).unwrap();
+    assert!(gray_at(&wand, 0, 0) > gray_at(&gradient, 0, 0));
+    assert!(gray_at(&wand, 0, 127) < gray_at(&gradient, 0, 127));
+}
+
+#[test]
+fn test_gamma_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // Gamma correction raises each value to the power of 1 / gamma.
+    let gray = single_pixel("gray50");
+    let value = gray_at(&gray, 0, 0);
+    let mut wand = gray.clone();
+    wand.gamma_image(2.0).unwrap();
+    assert!((gray_at(&wand, 0, 0) - value.sqrt()).abs() < 0.01);
+    let mut wand = gray.clone();
+    wand.gamma_image(0.5).unwrap();
+    assert!((gray_at(&wand, 0, 0) - value * value).abs() < 0.01);
+}
+
+#[test]
+#[cfg(magick_7_0_8_0)]
+fn test_local_contrast_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut soft = halves_image(64, 191);
+    soft.blur_image(0.0, 2.0).unwrap();
+    let contrast = |wand: &MagickWand| gray_at(wand, 34, 32) - gray_at(wand, 29, 32);
+    let mut wand = soft.clone();
+    wand.local_contrast_image(10.0, 50.0).unwrap();
+    assert!(contrast(&wand) > contrast(&soft));
+}
+
+#[test]
+#[cfg(magick_7_0_9_0)]
+fn test_clahe_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let gradient = low_contrast_gradient();
+    let contrast = |wand: &MagickWand| gray_at(wand, 0, 127) - gray_at(wand, 0, 0);
+    let mut wand = gradient.clone();
+    wand.clahe_image(32, 32, 128, 3.0).unwrap();
+    assert!(contrast(&wand) > contrast(&gradient));
+}
+
+#[test]
+fn test_color_decision_list_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let tinted = single_pixel("rgb(200,150,100)");
+    let channels = |wand: &MagickWand| {
+        let pixel = wand.get_image_pixel_color(0, 0).unwrap();
+        (pixel.get_red(), pixel.get_green(), pixel.get_blue())
+    };
+    // Each channel becomes (value * slope + offset) ^ power.
+    let mut wand = tinted.clone();
+    wand.color_decision_list_image(
+        r#"<ColorCorrectionCollection xmlns="urn:ASC:CDL:v1.2">
+          <ColorCorrection id="sop">
+            <SOPNode>
+              <Slope>0.5 1 2</Slope>
+              <Offset>0.1 0 0</Offset>
+              <Power>2 1 1</Power>
+            </SOPNode>
+            <SATNode>
+              <Saturation>1</Saturation>
+            </SATNode>
+          </ColorCorrection>
+        </ColorCorrectionCollection>"#,
+    )
+    .unwrap();
+    let (red, green, blue) = channels(&wand);
+    assert!((red - (200.0 / 255.0 * 0.5 + 0.1_f64).powi(2)).abs() < 0.01);
+    assert!((green - 150.0 / 255.0).abs() < 0.01);
+    assert!((blue - 100.0 / 255.0 * 2.0).abs() < 0.01);
+
+    // Zero saturation turns the image gray.
+    let mut wand = tinted.clone();
+    wand.color_decision_list_image(
+        r#"<ColorCorrectionCollection xmlns="urn:ASC:CDL:v1.2">
+          <ColorCorrection id="gray">
+            <SOPNode>
+              <Slope>1 1 1</Slope>
+              <Offset>0 0 0</Offset>
+              <Power>1 1 1</Power>
+            </SOPNode>
+            <SATNode>
+              <Saturation>0</Saturation>
+            </SATNode>
+          </ColorCorrection>
+        </ColorCorrectionCollection>"#,
+    )
+    .unwrap();
+    let (red, green, blue) = channels(&wand);
+    assert!((red - green).abs() < 0.01 && (green - blue).abs() < 0.01);
+}
+
+#[test]
+#[cfg(magick_7_1_0_0)]
+fn test_white_balance_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // The gray world assumption pulls a tinted image towards neutral.
+    let mut wand = single_pixel("rgb(200,150,100)");
+    wand.white_balance_image().unwrap();
+    let pixel = wand.get_image_pixel_color(0, 0).unwrap();
+    assert!((pixel.get_red() - pixel.get_blue()).abs() < (200.0 - 100.0) / 255.0);
+}
+
+/// A 64x64 vertical gradient from black at the top to white at the bottom.
+fn black_to_white_gradient() -> MagickWand {
+    let mut wand = MagickWand::new();
+    wand.set_size(64, 64).unwrap();
+    wand.read_image("gradient:black-white").unwrap();
+    wand
+}
+
+/// The gray levels of row `y` of a 64 pixel wide image.
+fn row_of(wand: &MagickWand, y: isize) -> Vec<u8> {
+    wand.export_image_pixels(0, y, 64, 1, "I").unwrap()
+}
+
+#[test]
+fn test_threshold_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut wand = black_to_white_gradient();
+    wand.threshold_image(0.5).unwrap();
+    assert!(row_of(&wand, 30).iter().all(|&value| value == 0));
+    assert!(row_of(&wand, 33).iter().all(|&value| value == 255));
+}
+
+#[test]
+fn test_threshold_image_channel() {
+    use magick_rust::ChannelType;
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // Only the given channel is thresholded, by its own value.
+    let mut wand = single_pixel("rgb(200,100,50)");
+    wand.threshold_image_channel(ChannelType::Green, 0.5)
+        .unwrap();
+    let pixel = wand.get_image_pixel_color(0, 0).unwrap();
+    assert!((pixel.get_red() - 200.0 / 255.0).abs() < 0.01);
+    assert_eq!(0.0, pixel.get_green());
+    assert!((pixel.get_blue() - 50.0 / 255.0).abs() < 0.01);
+    let mut wand = single_pixel("rgb(200,100,50)");
+    wand.threshold_image_channel(ChannelType::Red, 0.5).unwrap();
+    let pixel = wand.get_image_pixel_color(0, 0).unwrap();
+    assert_eq!(1.0, pixel.get_red());
+    assert!((pixel.get_green() - 100.0 / 255.0).abs() < 0.01);
+}
+
+#[test]
+fn test_adaptive_threshold_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // Unevenly lit paper from gray25 at the top to gray75 at the bottom, with
+    // a darker 2x2 spot in the lighter half. A global threshold at 50% would
+    // turn the whole top half black.
+    let mut pixels = Vec::new();
+    for y in 0..64 {
+        for x in 0..64 {
+            let spot = (40..42).contains(&x) && (40..42).contains(&y);
+            let value = 64 + 2 * y as u8 - if spot { 40 } else { 0 };
+            pixels.extend([value; 3]);
+        }
+    }
+    let mut wand = MagickWand::new();
+    wand.new_image(64, 64, &PixelWand::new()).unwrap();
+    wand.import_image_pixels(0, 0, 64, 64, &pixels, "RGB")
+        .unwrap();
+    wand.adaptive_threshold_image(8, 8, -0.01).unwrap();
+    let binary = wand.export_image_pixels(0, 0, 64, 64, "I").unwrap();
+    for (index, &value) in binary.iter().enumerate() {
+        let (x, y) = (index % 64, index / 64);
+        let spot = (40..42).contains(&x) && (40..42).contains(&y);
+        assert_eq!(if spot { 0 } else { 255 }, value, "at ({x}, {y})");
+    }
+}
+
+#[test]
+fn test_random_threshold_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // Values below `low` always turn black and values above `high` always
+    // turn white; only those in between are left to chance.
+    let mut wand = black_to_white_gradient();
+    wand.random_threshold_image(0.25, 0.75).unwrap();
+    assert!(row_of(&wand, 8).iter().all(|&value| value == 0));
+    assert!(row_of(&wand, 56).iter().all(|&value| value == 255));
+    assert!(
+        row_of(&wand, 32)
+            .iter()
+            .all(|&value| value == 0 || value == 255)
+    );
+}
+
+#[test]
+fn test_black_and_white_threshold_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let gradient = black_to_white_gradient();
+    let mut level = PixelWand::new();
+    level.set_color("gray50").unwrap();
+    // Values below the threshold turn black, the others are untouched.
+    let mut wand = gradient.clone();
+    wand.black_threshold_image(&level).unwrap();
+    assert_eq!(0.0, gray_at(&wand, 0, 16));
+    assert_eq!(gray_at(&gradient, 0, 48), gray_at(&wand, 0, 48));
+    // Values above the threshold turn white, the others are untouched.
+    let mut wand = gradient.clone();
+    wand.white_threshold_image(&level).unwrap();
+    assert_eq!(1.0, gray_at(&wand, 0, 48));
+    assert_eq!(gray_at(&gradient, 0, 16), gray_at(&wand, 0, 16));
+}
+
+#[test]
+#[cfg(magick_7_0_10_37)]
+fn test_range_and_color_threshold_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // Values inside the range turn white, values outside it black.
+    let mut wand = black_to_white_gradient();
+    wand.range_threshold_image(0.2, 0.2, 0.8, 0.8).unwrap();
+    assert!(row_of(&wand, 8).iter().all(|&value| value == 0));
+    assert!(row_of(&wand, 32).iter().all(|&value| value == 255));
+    assert!(row_of(&wand, 56).iter().all(|&value| value == 0));
+
+    let mut start = PixelWand::new();
+    start.set_color("gray25").unwrap();
+    let mut stop = PixelWand::new();
+    stop.set_color("gray75").unwrap();
+    let mut wand = black_to_white_gradient();
+    wand.color_threshold_image(&start, &stop).unwrap();
+    assert!(row_of(&wand, 8).iter().all(|&value| value == 0));
+    assert!(row_of(&wand, 32).iter().all(|&value| value == 255));
+    assert!(row_of(&wand, 56).iter().all(|&value| value == 0));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_ordered_dither_with_threshold_maps() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let maps = magick_rust::magick_threshold_maps().unwrap();
+    let map = maps.iter().find(|map| map.name == "o4x4").unwrap();
+    assert!(!map.description.is_empty());
+    // The dither pattern only uses black and white, in proportion to the
+    // original gray level.
+    let mut wand = black_to_white_gradient();
+    wand.ordered_dither_image(&map.name).unwrap();
+    let pixels = wand.export_image_pixels(0, 0, 64, 64, "I").unwrap();
+    assert!(pixels.iter().all(|&value| value == 0 || value == 255));
+    assert!(row_of(&wand, 0).iter().all(|&value| value == 0));
+    let white = row_of(&wand, 63)
+        .iter()
+        .filter(|&&value| value == 255)
+        .count();
+    assert!(white >= 48);
+}
+
+#[test]
+fn test_edges_and_shapes() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut white = PixelWand::new();
+    white.set_color("white").unwrap();
+    let mut black = PixelWand::new();
+    black.set_color("black").unwrap();
+    // Two black squares on a white background.
+    let mut shapes = MagickWand::new();
+    shapes.new_image(100, 100, &white).unwrap();
+    let mut draw = magick_rust::DrawingWand::new();
+    draw.set_fill_color(&black);
+    draw.draw_rectangle(10.0, 10.0, 39.0, 39.0);
+    draw.draw_rectangle(60.0, 50.0, 89.0, 89.0);
+    shapes.draw_image(&draw).unwrap();
+
+    let mut edges = shapes.clone();
+    edges.canny_edge_image(0.0, 1.0, 0.1, 0.3).unwrap();
+    let pixel = edges.get_image_pixel_color(25, 10).unwrap();
+    assert!(pixel.get_red() > 0.5, "missing edge on the square border");
+    let pixel = edges.get_image_pixel_color(25, 25).unwrap();
+    assert!(pixel.get_red() < 0.5, "edge inside the square");
+
+    let mut lines = edges.clone();
+    let found = lines.hough_line_image(9, 9, 20).unwrap();
+    assert!(!found.is_empty());
+    for line in &found {
+        assert!(line.votes >= 20);
+    }
+    // The top of the first square is a horizontal line at y = 10.
+    assert!(found.iter().any(|line| {
+        (line.start.1 - line.end.1).abs() < 1.0 && (line.start.1 - 10.0).abs() < 2.0
+    }));
+
+    #[cfg(magick_7_1_0_0)]
+    {
+        let mut labeled = shapes.clone();
+        let components = labeled.connected_components_image(4).unwrap();
+        assert_eq!(components.len(), 3);
+        let square = components
+            .iter()
+            .find(|component| component.bbox == (60, 50, 30, 40))
+            .unwrap();
+        assert_eq!(square.area, 1200.0);
+        assert!((square.centroid.0 - 74.5).abs() < 0.5);
+        assert!((square.centroid.1 - 69.5).abs() < 0.5);
+        assert!(square.mean_color.red < 0.1);
+        let background = components
+            .iter()
+            .max_by(|a, b| a.area.total_cmp(&b.area))
+            .unwrap();
+        assert!(background.mean_color.red > 0.9);
+    }
+}
+
+#[test]
+fn test_fourier_transform() {
+    use magick_rust::{ComplexOperator, QUANTUM_RANGE, magick_version};
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut wand = MagickWand::new();
+    wand.set_size(64, 64).unwrap();
+    wand.read_image("gradient:black-white").unwrap();
+    if !magick_version().unwrap().fftw() {
+        let error = wand.forward_fourier_transform_image(true).unwrap_err();
+        assert!(error.to_string().contains("FFTW"));
+        return;
+    }
+
+    let spectrum = wand.forward_fourier_transform_image(true).unwrap();
+    assert_eq!(spectrum.get_number_images(), 2);
+    let frame = |index: usize| {
+        let images = spectrum.images();
+        MagickWand::new_from_image(&images.get(index).unwrap().get_image().unwrap()).unwrap()
+    };
+    let (magnitude, phase) = (frame(0), frame(1));
+    let restored = magnitude
+        .inverse_fourier_transform_image(&phase, true)
+        .unwrap();
+    assert_eq!(restored.get_image_width(), 64);
+    let mean = |wand: &MagickWand| wand.get_image_mean().unwrap().0 / QUANTUM_RANGE;
+    assert!((mean(&restored) - mean(&wand)).abs() < 0.02);
+
+    let parts = wand.forward_fourier_transform_image(false).unwrap();
+    let conjugate = parts.complex_images(ComplexOperator::Conjugate).unwrap();
+    assert_eq!(conjugate.get_number_images(), 2);
+}
+
+#[test]
+fn test_multi_image_evaluation() {
+    use magick_rust::{MagickEvaluateOperator, QUANTUM_RANGE};
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let stack = |levels: &[&str]| {
+        let mut wand = MagickWand::new();
+        for level in levels {
+            let mut color = PixelWand::new();
+            color.set_color(level).unwrap();
+            let mut frame = MagickWand::new();
+            frame.new_image(8, 8, &color).unwrap();
+            wand.add_image(&frame).unwrap();
+        }
+        wand
+    };
+    let mean = |wand: &MagickWand| wand.get_image_mean().unwrap().0 / QUANTUM_RANGE;
+
+    let exposures = stack(&["gray20", "gray30", "gray90"]);
+    for (op, expected) in [
+        (MagickEvaluateOperator::Mean, (0.2 + 0.3 + 0.9) / 3.0),
+        (MagickEvaluateOperator::Median, 0.3),
+        (MagickEvaluateOperator::Min, 0.2),
+        (MagickEvaluateOperator::Max, 0.9),
+    ] {
+        let combined = exposures.evaluate_images(op).unwrap();
+        assert_eq!(combined.get_number_images(), 1);
+        let value = mean(&combined);
+        assert!((value - expected).abs() < 0.01, "{op:?}: {value}");
+    }
+    assert_eq!(exposures.get_number_images(), 3);
+
+    // An even blend of two frames.
+    let mut blend = stack(&["gray20", "gray40"]);
+    blend.polynomial_image(&[0.5, 1.0, 0.5, 1.0]).unwrap();
+    assert_eq!(blend.get_number_images(), 1);
+    assert!((mean(&blend) - 0.3).abs() < 0.01);
+    // Squaring a single frame.
+    let mut squared = stack(&["gray50"]);
+    squared.polynomial_image(&[1.0, 2.0]).unwrap();
+    assert!((mean(&squared) - 0.25).abs() < 0.01);
+    assert!(stack(&["gray50"]).polynomial_image(&[1.0]).is_err());
+}
+
+#[test]
+fn test_palette_remapping() {
+    use magick_rust::{Color, DitherMethod};
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let gpl = "GIMP Palette\nName: Traffic\nColumns: 3\n# lights\n255   0   0\tRed\n255 255   0 Yellow\n  0 255   0 Green\n";
+    let colors = Color::parse_gpl(gpl).unwrap();
+    assert_eq!(
+        colors,
+        [
+            Color::rgb(1.0, 0.0, 0.0),
+            Color::rgb(1.0, 1.0, 0.0),
+            Color::rgb(0.0, 1.0, 0.0)
+        ]
+    );
+    assert!(Color::parse_gpl("255 0 0 Red\n").is_err());
+    let mut act = vec![0u8; 772];
+    act[..6].copy_from_slice(&[0, 0, 255, 255, 255, 255]);
+    act[768..].copy_from_slice(&[0, 2, 0, 1]);
+    let colors = Color::parse_act(&act).unwrap();
+    assert_eq!(colors.len(), 2);
+    assert_eq!(colors[0], Color::rgb(0.0, 0.0, 1.0));
+    assert_eq!(colors[1].alpha, 0.0);
+    assert!(Color::parse_act(&act[..100]).is_err());
+
+    let close = |pixel: &PixelWand, expected: Color| {
+        let color = Color::from_pixel_wand(pixel);
+        (color.red - expected.red).abs() < 1e-3
+            && (color.green - expected.green).abs() < 1e-3
+            && (color.blue - expected.blue).abs() < 1e-3
+    };
+
+    // Named after the process so that concurrent test runs do not collide.
+    let path = std::env::temp_dir().join(format!("magick-rust-traffic-{}.gpl", std::process::id()));
+    std::fs::write(&path, gpl).unwrap();
+    let palette = MagickWand::new_from_palette_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(palette.get_image_width(), 3);
+    let pixel = palette.get_image_pixel_color(1, 0).unwrap();
+    assert!(close(&pixel, Color::rgb(1.0, 1.0, 0.0)));
+    assert!(MagickWand::new_from_palette(&[]).is_err());
+
+    let mut photo = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut photo);
+    photo
+        .resize_image(128, 96, magick_rust::FilterType::Lanczos)
+        .unwrap();
+    let mut wand = photo.clone();
+    wand.remap_image(&palette, DitherMethod::No).unwrap();
+    assert!(wand.get_image_colors() <= 3);
+    let mut wand = photo.clone();
+    wand.posterize_image(2, DitherMethod::No).unwrap();
+    assert!(wand.get_image_colors() <= 8);
+
+    let mut white = PixelWand::new();
+    white.set_color("white").unwrap();
+    let mut red = PixelWand::new();
+    red.set_color("red").unwrap();
+    let mut canvas = MagickWand::new();
+    canvas.new_image(8, 8, &white).unwrap();
+    let mut wand = canvas.clone();
+    wand.opaque_paint_image(&white, &red, 0.0, false).unwrap();
+    let pixel = wand.get_image_pixel_color(4, 4).unwrap();
+    assert!(close(&pixel, Color::rgb(1.0, 0.0, 0.0)));
+    let mut wand = canvas.clone();
+    wand.opaque_paint_image(&white, &red, 0.0, true).unwrap();
+    let pixel = wand.get_image_pixel_color(4, 4).unwrap();
+    assert!(close(&pixel, Color::rgb(1.0, 1.0, 1.0)));
+
+    let mut blend = PixelWand::new();
+    blend.set_color("rgb(50%,50%,50%)").unwrap();
+    let mut wand = canvas.clone();
+    wand.colorize_image(&red, &blend).unwrap();
+    let pixel = wand.get_image_pixel_color(4, 4).unwrap();
+    assert!(pixel.get_red() > 0.9 && pixel.get_green() < 0.6);
+    let mut gray = PixelWand::new();
+    gray.set_color("gray50").unwrap();
+    let mut wand = MagickWand::new();
+    wand.new_image(8, 8, &gray).unwrap();
+    wand.tint_image(&red, &blend).unwrap();
+    let pixel = wand.get_image_pixel_color(4, 4).unwrap();
+    assert!(pixel.get_red() > pixel.get_green());
+}
+
+#[test]
+fn test_dominant_colors() {
+    use magick_rust::Color;
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // Half red, 30% blue and 20% green.
+    let mut red = PixelWand::new();
+    red.set_color("red").unwrap();
+    let mut wand = MagickWand::new();
+    wand.new_image(100, 10, &red).unwrap();
+    let mut draw = magick_rust::DrawingWand::new();
+    let mut blue = PixelWand::new();
+    blue.set_color("blue").unwrap();
+    draw.set_fill_color(&blue);
+    draw.draw_rectangle(50.0, 0.0, 79.0, 9.0);
+    let mut green = PixelWand::new();
+    green.set_color("lime").unwrap();
+    draw.set_fill_color(&green);
+    draw.draw_rectangle(80.0, 0.0, 99.0, 9.0);
+    wand.draw_image(&draw).unwrap();
+
+    let colors = wand.dominant_colors(3).unwrap();
+    assert_eq!(colors.len(), 3);
+    let expected = [
+        (Color::rgb(1.0, 0.0, 0.0), 0.5),
+        (Color::rgb(0.0, 0.0, 1.0), 0.3),
+        (Color::rgb(0.0, 1.0, 0.0), 0.2),
+    ];
+    for ((color, share), (expected_color, expected_share)) in colors.iter().zip(expected) {
+        assert!(
+            color.perceptual_distance(&expected_color) < 5.0,
+            "{color:?}"
+        );
+        assert!((share - expected_share).abs() < 0.03, "{share}");
+    }
+    assert!(wand.dominant_colors(0).is_err());
+
+    // A large photo is sampled down, and its shares still add up.
+    let mut photo = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut photo);
+    let colors = photo.dominant_colors(5).unwrap();
+    assert!(!colors.is_empty() && colors.len() <= 5);
+    assert!(colors.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    let total: f64 = colors.iter().map(|(_, share)| share).sum();
+    assert!(total <= 1.0 + 1e-9);
+    assert_eq!(photo.get_image_width(), 512);
+
+    // Distinct colors come before near duplicates of earlier ones.
+    let mut palette = vec![
+        (Color::rgb(1.0, 0.0, 0.0), 0.4),
+        (Color::rgb(0.95, 0.05, 0.0), 0.35),
+        (Color::rgb(0.0, 0.0, 1.0), 0.25),
+    ];
+    Color::sort_by_distinctness(&mut palette);
+    assert_eq!(palette[0].0, Color::rgb(1.0, 0.0, 0.0));
+    assert_eq!(palette[1].0, Color::rgb(0.0, 0.0, 1.0));
+    assert!(
+        (Color::rgb(0.0, 0.0, 0.0).perceptual_distance(&Color::rgb(1.0, 1.0, 1.0)) - 100.0).abs()
+            < 0.1
+    );
+}
+
+#[test]
+fn test_floodfill_paint_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // A white image with a red square in the middle. Flood-filling the white
+    // background from a corner with a transparent color is the "remove the
+    // background" operation; the red foreground must be left untouched.
+    let mut wand = MagickWand::new();
+    let mut white = PixelWand::new();
+    white.set_color("white").unwrap();
+    wand.new_image(10, 10, &white).unwrap();
+    wand.set_image_alpha_channel(magick_rust::AlphaChannelOption::Activate)
+        .unwrap();
+
+    let mut red = PixelWand::new();
+    red.set_color("red").unwrap();
+    let mut draw = magick_rust::DrawingWand::new();
+    draw.set_fill_color(&red);
+    draw.draw_rectangle(3.0, 3.0, 6.0, 6.0);
+    wand.draw_image(&draw).unwrap();
+
+    let mut transparent = PixelWand::new();
+    transparent.set_color("none").unwrap();
+    wand.floodfill_paint_image(&transparent, 0.0, &white, 0, 0, false)
+        .unwrap();
+
+    // The flooded corner is now transparent...
+    let corner = wand.get_image_pixel_color(0, 0).unwrap();
+    assert_eq!(0.0, corner.get_alpha());
+    // ...while the red foreground square remains fully opaque.
+    let center = wand.get_image_pixel_color(4, 4).unwrap();
+    assert_eq!(1.0, center.get_alpha());
+    assert!(center.get_red() > 0.5 && center.get_green() < 0.5 && center.get_blue() < 0.5);
+}
+
+#[test]
+fn test_transparent_paint_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    // A white image with a red square in the middle. Painting white as
+    // transparent is the "-transparent white" operation; unlike a floodfill it
+    // is not connectivity-bound, so every white pixel disappears while the red
+    // foreground stays opaque.
+    let mut wand = MagickWand::new();
+    let mut white = PixelWand::new();
+    white.set_color("white").unwrap();
+    wand.new_image(10, 10, &white).unwrap();
+    wand.set_image_alpha_channel(magick_rust::AlphaChannelOption::Activate)
+        .unwrap();
+
+    let mut red = PixelWand::new();
+    red.set_color("red").unwrap();
+    let mut draw = magick_rust::DrawingWand::new();
+    draw.set_fill_color(&red);
+    draw.draw_rectangle(3.0, 3.0, 6.0, 6.0);
+    wand.draw_image(&draw).unwrap();
+
+    // alpha == 0.0 makes the matched (white) pixels fully transparent.
+    wand.transparent_paint_image(&white, 0.0, 0.0, false)
+        .unwrap();
+
+    // Every white pixel is now transparent. The alpha comes back from
+    // ImageMagick's quantum->normalized-float conversion, so it may be a tiny
+    // denormal rather than exactly 0.0; compare within a tolerance.
+    let corner = wand.get_image_pixel_color(0, 0).unwrap();
+    assert!(corner.get_alpha() < 1e-6);
+    // ...while the red foreground square remains fully opaque.
+    let center = wand.get_image_pixel_color(4, 4).unwrap();
+    assert!(center.get_alpha() > 1.0 - 1e-6);
+    assert!(center.get_red() > 0.5 && center.get_green() < 0.5 && center.get_blue() < 0.5);
+}
+
+#[test]
+fn test_set_background_color() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut wand = MagickWand::new();
+    let mut pw = PixelWand::new();
+    pw.set_color("none").unwrap();
+    wand.set_background_color(&pw).unwrap();
+    RUST_SVG.read_image(&mut wand);
+    let blob = wand.write_image_blob("rgba").unwrap();
+    assert_eq!(0u8, blob[0]);
+    assert_eq!(0u8, blob[1]);
+    assert_eq!(0u8, blob[2]);
+    assert_eq!(0u8, blob[3]);
+}
+
+#[test]
+fn test_set_size() {
+    let mut wand = MagickWand::new();
+    assert!(wand.set_size(100, 100).is_ok());
+}
+
+#[test]
+fn test_clut_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut wand = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand);
+
+    let mut gradient = MagickWand::new();
+    assert!(gradient.set_size(128, 20).is_ok());
+    assert!(gradient.set_option("gradient:angle", "90").is_ok());
+    assert!(gradient.read_image("gradient:black-yellow").is_ok());
+
+    assert!(
+        wand.clut_image(&gradient, magick_rust::PixelInterpolateMethod::Bilinear)
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_negate_image() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut wand = MagickWand::new();
+    RUST_PNG.read_image(&mut wand);
+    wand.negate_image().unwrap();
+    let pixel_color = wand.get_image_pixel_color(0, 0).unwrap();
+    assert_eq!(
+        "srgb(255,255,255)",
+        pixel_color.get_color_as_string().unwrap()
+    );
+}
+
+#[test]
+// opt-in platforms that have resource limits support
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn test_resource_limits() {
+    use magick_rust::ResourceType;
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    MagickWand::set_resource_limit(ResourceType::Thread, 1).unwrap();
+    let mut wand = MagickWand::new();
+    RUST_PNG.read_image(&mut wand);
+}
+
+#[test]
+fn test_auto_level() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut wand = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand);
+    assert!(wand.auto_level().is_ok());
+}
+
+#[test]
+fn test_auto_gamma() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut wand = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand);
+    assert!(wand.auto_gamma().is_ok());
+}
+
+#[test]
+fn test_image_compose() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut wand = MagickWand::new();
+    wand.new_image(4, 4, &PixelWand::new()).unwrap();
+
+    let operators = [
+        magick_rust::CompositeOperator::Alpha,
+        magick_rust::CompositeOperator::MinusDst,
+        magick_rust::CompositeOperator::Over,
         magick_rust::CompositeOperator::Xor,
         magick_rust::CompositeOperator::Bumpmap,
         magick_rust::CompositeOperator::ChangeMask,
@@ -667,8 +2205,8 @@ fn test_image_list_frame_access() {
     });
     // rust.gif is a two-frame animation: frame 0 is the full 156x150 canvas,
     // frame 1 is an optimized 80x76 sub-frame.
-    let wand = MagickWand::new();
-    RUST_GIF.read_image(&wand);
+    let mut wand = MagickWand::new();
+    RUST_GIF.read_image(&mut wand);
 
     let images = wand.images();
     assert_eq!(2, images.count());
@@ -704,7 +2242,7 @@ fn test_image_list_coalesce_and_draw() {
         magick_wand_genesis();
     });
     let mut wand = MagickWand::new();
-    RUST_GIF.read_image(&wand);
+    RUST_GIF.read_image(&mut wand);
 
     // Coalescing expands every frame to the full canvas, so both frames become
     // 156x150 and can be edited uniformly.
@@ -745,7 +2283,7 @@ fn test_image_list_remove() {
         magick_wand_genesis();
     });
     let mut wand = MagickWand::new();
-    RUST_GIF.read_image(&wand);
+    RUST_GIF.read_image(&mut wand);
 
     assert_eq!(2, wand.images().count());
     wand.images_mut().remove(0).unwrap();
@@ -754,3 +2292,237 @@ fn test_image_list_remove() {
     // Removing past the end is an error rather than a panic.
     assert!(wand.images_mut().remove(5).is_err());
 }
+
+#[test]
+fn test_image_list_iterator() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut wand = MagickWand::new();
+    RUST_GIF.read_image(&mut wand);
+
+    let images = wand.images();
+    let widths: Vec<usize> = images.iter().map(|frame| frame.get_image_width()).collect();
+    assert_eq!(vec![156, 80], widths);
+
+    // The iterator is double-ended and exact-sized.
+    let mut iter = images.iter();
+    assert_eq!(2, iter.len());
+    assert_eq!(80, iter.next_back().unwrap().get_image_width());
+    assert_eq!(1, iter.len());
+    assert_eq!(156, iter.next().unwrap().get_image_width());
+    assert!(iter.next().is_none());
+    assert!(iter.next_back().is_none());
+
+    // `&Images` can be used directly in a for loop.
+    let mut count = 0;
+    for frame in &images {
+        assert!(frame.get_image_width() > 0);
+        count += 1;
+    }
+    assert_eq!(2, count);
+}
+
+/// Build a wand holding one blank frame per width, all 10 pixels high, so that
+/// frames can be told apart by their width alone.
+fn frames_of_widths(widths: &[usize]) -> MagickWand {
+    let mut wand = MagickWand::new();
+    for &width in widths {
+        let mut frame = MagickWand::new();
+        frame.new_image(width, 10, &PixelWand::new()).unwrap();
+        wand.images_mut().append(&frame).unwrap();
+    }
+    wand
+}
+
+fn frame_widths(wand: &MagickWand) -> Vec<usize> {
+    wand.images()
+        .iter()
+        .map(|frame| frame.get_image_width())
+        .collect()
+}
+
+#[test]
+fn test_image_list_editing() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut wand = frames_of_widths(&[1, 2, 3]);
+    assert_eq!(vec![1, 2, 3], frame_widths(&wand));
+
+    let extra = frames_of_widths(&[9]);
+    wand.images_mut().insert(0, &extra).unwrap();
+    assert_eq!(vec![9, 1, 2, 3], frame_widths(&wand));
+    wand.images_mut().insert(2, &extra).unwrap();
+    assert_eq!(vec![9, 1, 9, 2, 3], frame_widths(&wand));
+    wand.images_mut().insert(5, &extra).unwrap();
+    assert_eq!(vec![9, 1, 9, 2, 3, 9], frame_widths(&wand));
+    assert!(wand.images_mut().insert(7, &extra).is_err());
+
+    wand.images_mut()
+        .retain(|frame| frame.get_image_width() != 9)
+        .unwrap();
+    assert_eq!(vec![1, 2, 3], frame_widths(&wand));
+
+    wand.images_mut().swap(0, 2).unwrap();
+    assert_eq!(vec![3, 2, 1], frame_widths(&wand));
+    assert!(wand.images_mut().swap(0, 3).is_err());
+
+    wand.images_mut().reverse().unwrap();
+    assert_eq!(vec![1, 2, 3], frame_widths(&wand));
+
+    wand.images_mut().move_frame(0, 2).unwrap();
+    assert_eq!(vec![2, 3, 1], frame_widths(&wand));
+    wand.images_mut().move_frame(2, 0).unwrap();
+    assert_eq!(vec![1, 2, 3], frame_widths(&wand));
+    assert!(wand.images_mut().move_frame(3, 0).is_err());
+}
+
+#[test]
+fn test_frozen_wand_shared_between_threads() {
+    use std::sync::Arc;
+    use std::thread;
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let mut wand = MagickWand::new();
+    IMG_5745_JPG.read_image(&mut wand);
+    assert!(MagickWand::new().freeze().is_err());
+    let master = Arc::new(wand.freeze().unwrap());
+
+    let handles: Vec<_> = [64, 128, 256]
+        .into_iter()
+        .map(|size| {
+            let master = Arc::clone(&master);
+            thread::spawn(move || {
+                assert_eq!(512, master.get_image_width());
+                let mut thumb = master.thaw();
+                thumb
+                    .resize_image(size, size, magick_rust::FilterType::Lanczos)
+                    .unwrap();
+                assert_eq!(size, thumb.get_image_width());
+                master.write_image_blob("png").unwrap().len()
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert!(handle.join().unwrap() > 0);
+    }
+
+    // The master itself is untouched by the per-thread edits.
+    assert_eq!(512, master.get_image_width());
+    assert_eq!(384, master.get_image_height());
+    assert_eq!("JPEG", master.get_image_format().unwrap());
+}
+
+#[test]
+#[cfg(feature = "tokio")]
+fn test_async_wand() {
+    use magick_rust::AsyncMagickWand;
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let runtime = tokio::runtime::Builder::new_multi_thread().build().unwrap();
+    let mut data: Vec<u8> = Vec::new();
+    IMG_5745_JPG.file().read_to_end(&mut data).unwrap();
+
+    let blob = runtime.block_on(async {
+        let mut wand = AsyncMagickWand::new(MagickWand::new());
+        wand.read_image_blob(data).await.unwrap();
+        wand.resize_image(256, 192, magick_rust::FilterType::Lanczos)
+            .await
+            .unwrap();
+        let width = wand.run(|wand| Ok(wand.get_image_width())).await.unwrap();
+        assert_eq!(256, width);
+        wand.write_image_blob("png").await.unwrap()
+    });
+    let mut wand = MagickWand::new();
+    wand.read_image_blob(&blob).unwrap();
+    assert_eq!(256, wand.get_image_width());
+
+    // A cancelled wand refuses further work until the cancellation is reset.
+    runtime.block_on(async {
+        let mut wand = AsyncMagickWand::new(wand);
+        wand.cancellation_token().cancel();
+        assert!(
+            wand.resize_image(10, 10, magick_rust::FilterType::Point)
+                .await
+                .is_err()
+        );
+        wand.reset_cancellation();
+        wand.resize_image(10, 10, magick_rust::FilterType::Point)
+            .await
+            .unwrap();
+        assert_eq!(10, wand.into_inner().unwrap().get_image_width());
+    });
+
+    // Images cloned during an operation keep its progress monitor, which must
+    // stay harmless once the operation and its async wand are gone.
+    let mut wand = MagickWand::new();
+    wand.read_image_blob(&blob).unwrap();
+    let mut clone = runtime.block_on(async {
+        let mut wand = AsyncMagickWand::new(wand);
+        let clone = wand.run(|wand| Ok(wand.clone())).await.unwrap();
+        wand.cancellation_token().cancel();
+        clone
+    });
+    clone
+        .resize_image(20, 20, magick_rust::FilterType::Lanczos)
+        .unwrap();
+    clone.blur_image(2.0, 1.0).unwrap();
+    assert_eq!(20, clone.get_image_width());
+}
+
+#[test]
+#[cfg(all(feature = "log", not(feature = "tracing")))]
+fn test_install_log_handler() {
+    use std::sync::Mutex;
+
+    static TARGETS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    struct Capture;
+
+    impl log::Log for Capture {
+        fn enabled(&self, _metadata: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            TARGETS.lock().unwrap().push(record.target().to_string());
+        }
+
+        fn flush(&self) {}
+    }
+
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    log::set_logger(&Capture).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+    magick_rust::install_log_handler("Coder").unwrap();
+
+    let mut wand = MagickWand::new();
+    RUST_PNG.read_image(&mut wand);
+    magick_rust::install_log_handler("None").unwrap();
+
+    let targets = TARGETS.lock().unwrap();
+    assert!(targets.iter().any(|target| target == "magick_rust::coder"));
+}
+
+#[test]
+fn test_magick_version() {
+    START.call_once(|| {
+        magick_wand_genesis();
+    });
+    let version = magick_rust::magick_version().unwrap();
+    assert_eq!(7, version.major);
+    assert!(version.as_tuple() >= (7, 0, 0, 0));
+    assert!(version.description.starts_with("ImageMagick 7."));
+    assert!([8, 16, 32, 64].contains(&version.quantum_depth));
+    assert_eq!(version.hdri, version.has_feature("hdri"));
+    // The fixtures include PNG and JPEG images, so those delegates must exist.
+    assert!(version.has_delegate("png"));
+    assert!(version.require_delegates(&["png", "jpeg"]).is_ok());
+    assert!(version.require_delegates(&["no-such-delegate"]).is_err());
+    assert!(version.verify_build_config().is_ok());
+}